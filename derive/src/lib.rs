@@ -0,0 +1,187 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{
+    parse_macro_input, GenericArgument, Ident, ItemConst, LitInt, PathArguments, Token, Type,
+};
+
+/// Page size used when the attribute doesn't set `limit`, same as `DefaultPage`
+const DEFAULT_LIMIT: usize = 50;
+
+/// Arguments of `#[paginated(response = T, limit = N)]`, both optional
+struct Args {
+    response: Option<Type>,
+    limit: Option<LitInt>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Args {
+            response: None,
+            limit: None,
+        };
+
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match name.to_string().as_str() {
+                "response" => args.response = Some(input.parse()?),
+                "limit" => args.limit = Some(input.parse()?),
+                _ => {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "expected `response` or `limit`",
+                    ))
+                }
+            }
+            if !input.is_empty() {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+/// Generates the query plumbing for a `const` map, so
+///
+/// ```ignore
+/// #[paginated(response = AccountInfo)]
+/// pub const ACCOUNTS: Map<'static, Addr, Account> = Map::new("accounts");
+/// ```
+///
+/// adds an `AccountsResponse` alias for `NextPage<AccountInfo, Addr>` and a
+/// `page_accounts(deps, page)` function paginating the map. Values are converted into the
+/// response through `Into`, `response` defaults to the value type and `limit` to 50
+#[proc_macro_attribute]
+pub fn paginated(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as Args);
+    let item = parse_macro_input!(item as ItemConst);
+    expand(args, item)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(args: Args, item: ItemConst) -> syn::Result<TokenStream2> {
+    let (key, value) = map_types(&item.ty)?;
+    let vis = &item.vis;
+    let map = &item.ident;
+    let name = map.to_string();
+    let response = args.response.unwrap_or_else(|| value.clone());
+    let limit = match args.limit {
+        Some(limit) => quote!(#limit),
+        None => quote!(#DEFAULT_LIMIT),
+    };
+    let alias = Ident::new(&format!("{}Response", pascal_case(&name)), map.span());
+    let page_fn = Ident::new(&format!("page_{}", name.to_lowercase()), map.span());
+
+    Ok(quote! {
+        #item
+
+        #vis type #alias = ::cw_query::NextPage<
+            #response,
+            <#key as ::cw_query::__private::KeyDeserialize>::Output,
+        >;
+
+        #vis fn #page_fn(
+            deps: ::cw_query::__private::Deps<'_>,
+            page: ::cw_query::Page<#limit, #key>,
+        ) -> ::cw_query::__private::StdResult<#alias> {
+            ::cw_query::PaginatedQuery::into_pagination(
+                page,
+                deps.storage,
+                &#map,
+                |_, value: #value| -> #response { ::core::convert::Into::into(value) },
+            )
+        }
+    })
+}
+
+/// Key and value of a `Map<K, V>` or `Map<'a, K, V>` type
+fn map_types(ty: &Type) -> syn::Result<(Type, Type)> {
+    let error = || syn::Error::new_spanned(ty, "expected a `Map<K, V>` type");
+    let Type::Path(path) = ty else {
+        return Err(error());
+    };
+    let segment = path.path.segments.last().ok_or_else(error)?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(error());
+    };
+
+    let mut types = args.args.iter().filter_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(key), Some(value), None) => Ok((key, value)),
+        _ => Err(error()),
+    }
+}
+
+/// `USER_BALANCES` into `UserBalances`
+fn pascal_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .flat_map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().into_iter().flat_map(char::to_uppercase);
+            first.chain(chars.flat_map(char::to_lowercase))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{expand, map_types, pascal_case, Args};
+    use quote::ToTokens;
+    use syn::{parse_quote, Type};
+
+    #[test]
+    fn names() {
+        assert_eq!(pascal_case("ACCOUNTS"), "Accounts");
+        assert_eq!(pascal_case("USER_BALANCES"), "UserBalances");
+        assert_eq!(pascal_case("_PENDING__CLAIMS_"), "PendingClaims");
+    }
+
+    #[test]
+    fn map_key_and_value() {
+        let types = |ty: Type| {
+            let (key, value) = map_types(&ty).unwrap();
+            (
+                key.to_token_stream().to_string(),
+                value.to_token_stream().to_string(),
+            )
+        };
+        assert_eq!(
+            types(parse_quote!(Map<'static, (Addr, u64), Uint128>)),
+            ("(Addr , u64)".to_string(), "Uint128".to_string())
+        );
+        assert_eq!(
+            types(parse_quote!(cw_storage_plus::Map<String, u8>)),
+            ("String".to_string(), "u8".to_string())
+        );
+
+        assert!(map_types(&parse_quote!(Item<'static, u8>)).is_err());
+        assert!(map_types(&parse_quote!(u8)).is_err());
+    }
+
+    #[test]
+    fn expand_items() {
+        let args: Args = syn::parse_str("response = AccountInfo, limit = 20").unwrap();
+        let tokens = expand(
+            args,
+            parse_quote!(
+                pub const USER_ACCOUNTS: Map<'static, Addr, Account> = Map::new("a");
+            ),
+        )
+        .unwrap()
+        .to_token_stream()
+        .to_string();
+        assert!(tokens.contains("pub type UserAccountsResponse"));
+        assert!(tokens.contains("pub fn page_user_accounts"));
+        assert!(tokens.contains("Page < 20 , Addr >"));
+
+        assert!(syn::parse_str::<Args>("order = desc").is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::Map;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultAggregatePage<K, A> = AggregatePage<50, K, A>;
+
+/// Cursor carrying the accumulator folded so far, so a large aggregation continues across
+/// queries without the contract keeping any state. The accumulator is client provided and
+/// only vouches for the caller's own result
+#[cw_serde]
+pub struct AggregateCursor<K, A> {
+    pub key: K,
+    pub acc: A,
+}
+
+/// Folds `qty` entries per query, starting from the cursor's accumulator or the initial
+/// value on the first query
+#[cw_serde]
+pub struct AggregatePage<const LIMIT: usize, K, A> {
+    pub start: Option<AggregateCursor<K, A>>,
+    pub qty: Option<usize>,
+}
+
+/// `acc` is the exact total once `next` is `None`, otherwise a partial one to resume from
+#[cw_serde]
+pub struct Aggregation<K, A> {
+    pub acc: A,
+    pub next: Option<AggregateCursor<K, A>>,
+}
+
+impl<const LIMIT: usize, Key, A> AggregatePage<LIMIT, Key, A> {
+    pub fn into_aggregation<'a, Value, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        init: A,
+        fold: Function,
+    ) -> StdResult<Aggregation<Key::Output, A>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        A: Clone,
+        Function: Fn(A, &Key::Output, Value) -> StdResult<A>,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let (start, mut acc) = match self.start {
+            Some(cursor) => (Some(cursor.key), cursor.acc),
+            None => (None, init),
+        };
+        let range = map
+            .range(
+                storage,
+                start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(limit);
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            let (key, value) = item?;
+            acc = fold(acc, &key, value)?;
+            scanned += 1;
+            end = Some(key);
+        }
+
+        // A short page means the range ran out
+        let next = end.filter(|_| scanned == limit).map(|key| AggregateCursor {
+            key,
+            acc: acc.clone(),
+        });
+        Ok(Aggregation { acc, next })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{AggregatePage, Map};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{StdResult, Uint128};
+
+    #[test]
+    fn into_aggregation() {
+        let mut deps = mock_dependencies();
+        let balances: Map<u32, Uint128> = Map::new("balances");
+
+        for i in 0..10 {
+            balances
+                .save(deps.as_mut().storage, i, &Uint128::new(i as u128))
+                .unwrap();
+        }
+
+        let sum = |acc: Uint128, _: &u32, value: Uint128| -> StdResult<Uint128> {
+            Ok(acc.checked_add(value)?)
+        };
+        let query: AggregatePage<4, u32, Uint128> = AggregatePage {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_aggregation(deps.as_ref().storage, &balances, Uint128::zero(), sum)
+            .unwrap();
+        assert_eq!(res.acc, Uint128::new(6));
+        let cursor = res.next.unwrap();
+        assert_eq!(cursor.key, 3);
+        assert_eq!(cursor.acc, Uint128::new(6));
+
+        let mut cursor = Some(cursor);
+        let mut total = Uint128::zero();
+        while let Some(start) = cursor {
+            let query: AggregatePage<4, u32, Uint128> = AggregatePage {
+                start: Some(start),
+                qty: None,
+            };
+            let res = query
+                .into_aggregation(deps.as_ref().storage, &balances, Uint128::zero(), sum)
+                .unwrap();
+            total = res.acc;
+            cursor = res.next;
+        }
+        assert_eq!(total, Uint128::new(45));
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{Map, NextPage, Page, PaginatedQuery, PrefixPage};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+use proptest::prelude::*;
+use std::fmt::Debug;
+
+impl<const LIMIT: usize, K> Arbitrary for Page<LIMIT, K>
+where
+    K: Arbitrary + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Option<K>>(), proptest::option::of(1..=LIMIT.max(1)))
+            .prop_map(|(start, qty)| Page { start, qty })
+            .boxed()
+    }
+}
+
+impl<const LIMIT: usize, Key, Prefix, Suffix> Arbitrary
+    for PrefixPage<'static, LIMIT, Key, Prefix, Suffix>
+where
+    Key: PrimaryKey<'static, Prefix = Prefix, Suffix = Suffix> + Debug + 'static,
+    Suffix: PrimaryKey<'static>
+        + KeyDeserialize
+        + Serialize
+        + DeserializeOwned
+        + Clone
+        + Arbitrary
+        + 'static,
+    Prefix: Serialize + Arbitrary + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Prefix>(),
+            any::<Option<Suffix>>(),
+            proptest::option::of(1..=LIMIT.max(1)),
+        )
+            .prop_map(|(prefix, start, qty)| PrefixPage { prefix, start, qty })
+            .boxed()
+    }
+}
+
+impl<D, K> Arbitrary for NextPage<D, K>
+where
+    D: Arbitrary + 'static,
+    K: Arbitrary + 'static,
+{
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Vec<D>>(), any::<Option<K>>())
+            .prop_map(|(data, next)| {
+                let qty = data.len();
+                NextPage {
+                    data,
+                    next,
+                    qty,
+                    scanned: qty,
+                    errors: vec![],
+                }
+            })
+            .boxed()
+    }
+}
+
+/// Invariant check that following every cursor of `page_size` pages yields the same
+/// entries as a single full range, meant to be run inside `proptest!` against any map
+pub fn check_full_scan<'a, Key, Value>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, Key, Value>,
+    page_size: usize,
+) -> Result<(), TestCaseError>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize<Output = Key> + Clone + PartialEq + Debug + 'static,
+    Value: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let expected: Vec<(Key, Value)> = map
+        .range(storage, None, None, Order::Ascending)
+        .collect::<Result<_, _>>()
+        .map_err(|e| TestCaseError::fail(e.to_string()))?;
+
+    let mut paged = vec![];
+    let mut start = None;
+    loop {
+        let page: Page<1, Key> = Page {
+            start,
+            qty: Some(page_size),
+        };
+        let res = page
+            .into_pagination(storage, map, |k, v| (k.clone(), v))
+            .map_err(|e| TestCaseError::fail(e.to_string()))?;
+
+        prop_assert!(res.qty <= page_size);
+        prop_assert_eq!(res.qty, res.data.len());
+        if res.data.is_empty() {
+            prop_assert!(res.next.is_none());
+            break;
+        }
+
+        prop_assert_eq!(res.next.as_ref(), res.data.last().map(|(k, _)| k));
+        start = res.next;
+        paged.extend(res.data);
+    }
+
+    prop_assert_eq!(paged, expected);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{check_full_scan, KeysQuery, Map, Page, PaginatedQuery, PrefixPage};
+    use cosmwasm_std::testing::mock_dependencies;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn pages_cover_full_range(keys in proptest::collection::btree_set(any::<u16>(), 0..200), page_size in 1usize..40) {
+            let mut deps = mock_dependencies();
+            let test_map: Map<u16, String> = Map::new("test_map");
+
+            for key in keys {
+                test_map
+                    .save(deps.as_mut().storage, key, &format!("string-{}", key))
+                    .unwrap();
+            }
+
+            check_full_scan(deps.as_ref().storage, &test_map, page_size)?;
+        }
+
+        #[test]
+        fn arbitrary_page_respects_limit(page in any::<Page<20, u8>>()) {
+            let mut deps = mock_dependencies();
+            let test_map: Map<u8, u8> = Map::new("test_map");
+
+            for i in 0..=u8::MAX {
+                test_map.save(deps.as_mut().storage, i, &i).unwrap();
+            }
+
+            let start = page.start;
+            let qty = page.qty.unwrap_or(20);
+            let keys: Vec<u8> = page
+                .keys(deps.as_ref().storage, &test_map)
+                .collect::<Result<_, _>>()
+                .unwrap();
+
+            prop_assert!(keys.len() <= qty);
+            prop_assert!(keys.iter().all(|k| start.map(|s| *k > s).unwrap_or(true)));
+        }
+
+        #[test]
+        fn arbitrary_page_visits_remaining_keys_once(
+            keys in proptest::collection::btree_set(any::<u8>(), 0..100),
+            page in any::<Page<20, u8>>(),
+        ) {
+            let mut deps = mock_dependencies();
+            let test_map: Map<u8, u8> = Map::new("test_map");
+
+            for key in &keys {
+                test_map.save(deps.as_mut().storage, *key, key).unwrap();
+            }
+
+            let expected: Vec<u8> = keys
+                .into_iter()
+                .filter(|k| page.start.map(|s| *k > s).unwrap_or(true))
+                .collect();
+            let mut visited = vec![];
+            let mut page = page;
+            loop {
+                let res = page
+                    .clone()
+                    .into_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+                    .unwrap();
+                if res.data.is_empty() {
+                    break;
+                }
+                // Pages never step back or repeat a key
+                prop_assert!(res.data.windows(2).all(|w| w[0] < w[1]));
+                prop_assert!(visited.last().map(|l| *l < res.data[0]).unwrap_or(true));
+                visited.extend(res.data);
+                page.start = res.next;
+            }
+
+            prop_assert_eq!(visited, expected);
+        }
+
+        #[test]
+        fn arbitrary_prefix_page_stays_in_prefix(
+            keys in proptest::collection::btree_set((0u8..4, any::<u8>()), 0..200),
+            page in any::<PrefixPage<20, (u8, u8), u8, u8>>(),
+        ) {
+            let mut deps = mock_dependencies();
+            let test_map: Map<(u8, u8), u8> = Map::new("test_map");
+
+            for (prefix, suffix) in &keys {
+                test_map
+                    .save(deps.as_mut().storage, (*prefix, *suffix), suffix)
+                    .unwrap();
+            }
+
+            // Pages from the strategy borrow for 'static, rebuild them around the local storage
+            let prefix = page.prefix % 4;
+            let expected: Vec<u8> = keys
+                .into_iter()
+                .filter(|(p, s)| *p == prefix && page.start.map(|st| *s > st).unwrap_or(true))
+                .map(|(_, s)| s)
+                .collect();
+            let mut visited = vec![];
+            let mut start = page.start;
+            loop {
+                let query: PrefixPage<20, (u8, u8), u8, u8> = PrefixPage {
+                    prefix,
+                    start,
+                    qty: page.qty,
+                };
+                let res = query
+                    .into_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+                    .unwrap();
+                prop_assert!(res.qty <= page.qty.unwrap_or(20));
+                if res.data.is_empty() {
+                    break;
+                }
+                prop_assert_eq!(res.next, res.data.last().copied());
+                visited.extend(res.data);
+                start = res.next;
+            }
+
+            prop_assert_eq!(visited, expected);
+        }
+    }
+}
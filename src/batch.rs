@@ -0,0 +1,141 @@
+use crate::error::validate_qty;
+use crate::{Map, NextPage, Page, PaginatedQuery, PrefixPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{StdError, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub type DefaultBatchPrefixPage<Prefix, Suffix> = BatchPrefixPage<50, 10, Prefix, Suffix>;
+
+/// Several prefixes paged in one query, each with its own cursor, so frontends loading
+/// e.g. the holdings of many users don't need a round trip per user. At most
+/// `MAX_PREFIXES` distinct prefixes are accepted per query
+#[cw_serde]
+pub struct BatchPrefixPage<const LIMIT: usize, const MAX_PREFIXES: usize, Prefix, Suffix> {
+    pub pages: Vec<(Prefix, Page<LIMIT, Suffix>)>,
+}
+
+impl<const LIMIT: usize, const MAX_PREFIXES: usize, Prefix, Suffix>
+    BatchPrefixPage<LIMIT, MAX_PREFIXES, Prefix, Suffix>
+{
+    pub fn into_batch_pagination<'a, Key, SO, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> Result<BTreeMap<Prefix, NextPage<Data, SO>>, QueryError>
+    where
+        Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+        Prefix: Serialize + Ord + Clone,
+        Suffix: PrimaryKey<'a> + KeyDeserialize<Output = SO> + Serialize + DeserializeOwned + Clone,
+        SO: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&SO, Value) -> Data + Copy,
+    {
+        // An empty batch is answered with an empty map
+        validate_qty(Some(self.pages.len()).filter(|len| *len > 0), MAX_PREFIXES)?;
+        let mut prefixes = BTreeSet::new();
+        if !self.pages.iter().all(|(prefix, _)| prefixes.insert(prefix)) {
+            return Err(StdError::generic_err("Duplicate prefix in batch").into());
+        }
+
+        let mut res = BTreeMap::new();
+
+        for (prefix, page) in self.pages {
+            let page: PrefixPage<LIMIT, Key, Prefix, Suffix> = PrefixPage {
+                prefix: prefix.clone(),
+                start: page.start,
+                qty: page.qty,
+            };
+            res.insert(prefix, page.into_pagination(storage, map, transform)?);
+        }
+
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{BatchPrefixPage, Map, Page, QueryError};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_batch_pagination() {
+        let mut deps = mock_dependencies();
+        let holdings: Map<(String, u32), u64> = Map::new("holdings");
+
+        for user in ["alice", "bob", "carol"] {
+            for i in 0..10 {
+                holdings
+                    .save(deps.as_mut().storage, (user.to_string(), i), &(i as u64))
+                    .unwrap();
+            }
+        }
+
+        let query: BatchPrefixPage<5, 3, String, u32> = BatchPrefixPage {
+            pages: vec![
+                (
+                    "alice".to_string(),
+                    Page {
+                        start: None,
+                        qty: None,
+                    },
+                ),
+                (
+                    "carol".to_string(),
+                    Page {
+                        start: Some(7),
+                        qty: None,
+                    },
+                ),
+                (
+                    "dave".to_string(),
+                    Page {
+                        start: None,
+                        qty: None,
+                    },
+                ),
+            ],
+        };
+
+        let res = query
+            .clone()
+            .into_batch_pagination(deps.as_ref().storage, &holdings, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res["alice"].data, vec![0, 1, 2, 3, 4]);
+        assert_eq!(res["carol"].data, vec![8, 9]);
+        assert_eq!(res["dave"].qty, 0);
+
+        let mut duplicate = query.clone();
+        duplicate.pages[2].0 = "alice".to_string();
+        assert!(duplicate
+            .into_batch_pagination(deps.as_ref().storage, &holdings, |k, _| *k)
+            .is_err());
+
+        let empty: BatchPrefixPage<5, 3, String, u32> = BatchPrefixPage { pages: vec![] };
+        assert!(empty
+            .into_batch_pagination(deps.as_ref().storage, &holdings, |k, _| *k)
+            .unwrap()
+            .is_empty());
+
+        let mut query = query;
+        query.pages.push((
+            "erin".to_string(),
+            Page {
+                start: None,
+                qty: None,
+            },
+        ));
+        assert_eq!(
+            query.into_batch_pagination(deps.as_ref().storage, &holdings, |k, _| *k),
+            Err(QueryError::LimitExceeded {
+                requested: 4,
+                max: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use crate::{namespace, Map, NextPage};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, Prefix, Prefixer, PrimaryKey};
+use std::marker::PhantomData;
+
+const DEFAULT_QUERY_LIMIT: usize = 50;
+const DEFAULT_QUERY_MAX_SCAN: usize = 500;
+
+/// Chainable query over a map, gathering the prefix, cursor, order, limit, filter and
+/// transform options behind one entry point, e.g.
+/// `Query::over(&ORDERS).prefix(owner).after(id).desc().limit(10).page(deps.storage)`
+pub struct Query<'q, Key, Value, Data = Value>
+where
+    Key: KeyDeserialize,
+    Value: Serialize + DeserializeOwned,
+{
+    namespace: Vec<u8>,
+    prefix: Prefix<Key, Value, Key>,
+    start: Option<Key>,
+    order: Order,
+    limit: usize,
+    max_scan: usize,
+    filter: Option<Box<dyn Fn(&Value) -> bool + 'q>>,
+    transform: Box<dyn Fn(&Key::Output, Value) -> Data + 'q>,
+}
+
+impl<'q, Key, Value> Query<'q, Key, Value>
+where
+    Key: KeyDeserialize,
+    Value: Serialize + DeserializeOwned,
+{
+    /// Ascending query over the whole map returning the values, up to 50 per page and
+    /// reading at most 500 entries
+    pub fn over(map: &Map<'_, Key, Value>) -> Self {
+        let namespace = namespace(map).to_vec();
+        Query {
+            prefix: Prefix::new(&namespace, &[]),
+            namespace,
+            start: None,
+            order: Order::Ascending,
+            limit: DEFAULT_QUERY_LIMIT,
+            max_scan: DEFAULT_QUERY_MAX_SCAN,
+            filter: None,
+            transform: Box::new(|_, value| value),
+        }
+    }
+
+    /// Narrows the query to the entries under `prefix`. The cursor is relative to the
+    /// narrowed key, so a cursor set before narrowing is dropped
+    pub fn prefix<'k>(
+        self,
+        prefix: <Key as PrimaryKey<'k>>::Prefix,
+    ) -> Query<'q, <Key as PrimaryKey<'k>>::Suffix, Value>
+    where
+        Key: PrimaryKey<'k>,
+    {
+        Query {
+            prefix: Prefix::new(&self.namespace, &prefix.prefix()),
+            namespace: self.namespace,
+            start: None,
+            order: self.order,
+            limit: self.limit,
+            max_scan: self.max_scan,
+            filter: self.filter,
+            transform: Box::new(|_, value| value),
+        }
+    }
+}
+
+impl<'q, Key, Value, Data> Query<'q, Key, Value, Data>
+where
+    Key: KeyDeserialize,
+    Value: Serialize + DeserializeOwned,
+{
+    /// Resumes after `key`, which is the previous page's `next`
+    pub fn after(mut self, key: Key) -> Self {
+        self.start = Some(key);
+        self
+    }
+
+    /// Iterates in descending key order, `after` then resumes below the cursor
+    pub fn desc(mut self) -> Self {
+        self.order = Order::Descending;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Caps the entries read by `page`, `keys` and `count`, so a sparse filter can't walk
+    /// the whole map. Once the cap is hit the cursor is returned to resume from
+    pub fn max_scan(mut self, max_scan: usize) -> Self {
+        self.max_scan = max_scan;
+        self
+    }
+
+    /// Keeps the entries whose value matches, filtered out entries still count towards
+    /// `scanned` and the scan cap but not towards the limit
+    pub fn filter(mut self, filter: impl Fn(&Value) -> bool + 'q) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Replaces the returned data with the output of `transform`
+    pub fn map<NewData>(
+        self,
+        transform: impl Fn(&Key::Output, Value) -> NewData + 'q,
+    ) -> Query<'q, Key, Value, NewData> {
+        Query {
+            namespace: self.namespace,
+            prefix: self.prefix,
+            start: self.start,
+            order: self.order,
+            limit: self.limit,
+            max_scan: self.max_scan,
+            filter: self.filter,
+            transform: Box::new(transform),
+        }
+    }
+
+    pub fn page<'s, 'k>(self, storage: &'s dyn Storage) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'k>,
+        Key::Output: 'static,
+        Value: 's,
+    {
+        let (min, max) = bounds(self.start, self.order);
+        let range = self
+            .prefix
+            .range(storage, min, max, self.order)
+            .take(self.max_scan);
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            if data.len() >= self.limit {
+                break;
+            }
+
+            let (key, value) = item?;
+            scanned += 1;
+            let matches = match &self.filter {
+                None => true,
+                Some(filter) => filter(&value),
+            };
+            if matches {
+                data.push((self.transform)(&key, value));
+            }
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+
+    /// Keys of the page, values are only loaded when a filter is set
+    pub fn keys<'s, 'k>(
+        self,
+        storage: &'s dyn Storage,
+    ) -> StdResult<NextPage<Key::Output, Key::Output>>
+    where
+        Key: PrimaryKey<'k>,
+        Key::Output: Clone + 'static,
+        Value: 's,
+    {
+        let (min, max) = bounds(self.start, self.order);
+        let Some(filter) = self.filter else {
+            let keys = self
+                .prefix
+                .keys(storage, min, max, self.order)
+                .take(self.limit.min(self.max_scan))
+                .collect::<StdResult<Vec<_>>>()?;
+            let len = keys.len();
+            return Ok(NextPage {
+                next: keys.last().cloned(),
+                data: keys,
+                qty: len,
+                scanned: len,
+                errors: vec![],
+            });
+        };
+
+        let range = self
+            .prefix
+            .range(storage, min, max, self.order)
+            .take(self.max_scan);
+        let mut keys = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            if keys.len() >= self.limit {
+                break;
+            }
+
+            let (key, value) = item?;
+            scanned += 1;
+            if filter(&value) {
+                keys.push(key.clone());
+            }
+            end = Some(key);
+        }
+
+        let len = keys.len();
+        Ok(NextPage {
+            data: keys,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+
+    /// Counts the matching entries past the cursor, the limit doesn't apply but at most
+    /// `max_scan` entries are read. The returned cursor is only set when the scan cap
+    /// stopped the count, counting on from it gives the rest
+    pub fn count<'s, 'k>(self, storage: &'s dyn Storage) -> StdResult<(usize, Option<Key::Output>)>
+    where
+        Key: PrimaryKey<'k>,
+        Key::Output: 'static,
+        Value: 's,
+    {
+        let (min, max) = bounds(self.start, self.order);
+        let mut count = 0;
+        let mut end = None;
+        let mut scanned = 0;
+
+        match self.filter {
+            None => {
+                for key in self
+                    .prefix
+                    .keys(storage, min, max, self.order)
+                    .take(self.max_scan)
+                {
+                    end = Some(key?);
+                    scanned += 1;
+                    count += 1;
+                }
+            }
+            Some(filter) => {
+                for item in self
+                    .prefix
+                    .range(storage, min, max, self.order)
+                    .take(self.max_scan)
+                {
+                    let (key, value) = item?;
+                    scanned += 1;
+                    if filter(&value) {
+                        count += 1;
+                    }
+                    end = Some(key);
+                }
+            }
+        }
+        Ok((count, end.filter(|_| scanned == self.max_scan)))
+    }
+}
+
+/// The cursor is a lower bound when ascending and an upper bound when descending
+fn bounds<'k, Key: PrimaryKey<'k>>(
+    start: Option<Key>,
+    order: Order,
+) -> (Option<Bound<'k, Key>>, Option<Bound<'k, Key>>) {
+    let bound = start.map(|s| Bound::Exclusive((s, PhantomData)));
+    match order {
+        Order::Ascending => (bound, None),
+        Order::Descending => (None, bound),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Query};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    const TEST_MAP: Map<'static, (u8, u32), u32> = Map::new("TEST_MAP");
+
+    #[test]
+    fn page() {
+        let mut deps = mock_dependencies();
+
+        for i in 0..100 {
+            TEST_MAP
+                .save(deps.as_mut().storage, ((i % 2) as u8, i), &i)
+                .unwrap();
+        }
+
+        let res = Query::over(&TEST_MAP)
+            .prefix(1)
+            .after(11)
+            .limit(3)
+            .page(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(res.data, vec![13, 15, 17]);
+        assert_eq!(res.next, Some(17));
+
+        let res = Query::over(&TEST_MAP)
+            .prefix(0)
+            .desc()
+            .filter(|v| v % 10 == 0)
+            .map(|k, v| format!("{}:{}", k, v))
+            .limit(2)
+            .page(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(res.data, vec!["90:90", "80:80"]);
+        assert_eq!(res.next, Some(80));
+        assert_eq!(res.scanned, 10);
+
+        let res = Query::over(&TEST_MAP)
+            .prefix(0)
+            .after(80)
+            .desc()
+            .limit(2)
+            .page(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(res.data, vec![78, 76]);
+    }
+
+    #[test]
+    fn keys_and_count() {
+        let mut deps = mock_dependencies();
+
+        for i in 0..100 {
+            TEST_MAP
+                .save(deps.as_mut().storage, ((i % 2) as u8, i), &i)
+                .unwrap();
+        }
+
+        let keys = Query::over(&TEST_MAP)
+            .limit(2)
+            .keys(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(keys.data, vec![(0, 0), (0, 2)]);
+        assert_eq!(keys.next, Some((0, 2)));
+
+        let keys = Query::over(&TEST_MAP)
+            .prefix(1)
+            .filter(|v| *v > 90)
+            .limit(2)
+            .keys(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(keys.data, vec![91, 93]);
+
+        let count = Query::over(&TEST_MAP)
+            .prefix(1)
+            .filter(|v| *v > 90)
+            .count(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(count, (5, None));
+
+        let count = Query::over(&TEST_MAP)
+            .prefix(1)
+            .after(89)
+            .count(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(count, (5, None));
+    }
+
+    #[test]
+    fn max_scan() {
+        let mut deps = mock_dependencies();
+
+        for i in 0..100 {
+            TEST_MAP
+                .save(deps.as_mut().storage, ((i % 2) as u8, i), &i)
+                .unwrap();
+        }
+
+        // Only 99 matches, so the page stops at the scan cap rather than the limit
+        let res = Query::over(&TEST_MAP)
+            .prefix(1)
+            .filter(|v| *v == 99)
+            .max_scan(10)
+            .page(deps.as_ref().storage)
+            .unwrap();
+        assert!(res.data.is_empty());
+        assert_eq!(res.next, Some(19));
+        assert_eq!(res.scanned, 10);
+
+        let res = Query::over(&TEST_MAP)
+            .prefix(1)
+            .after(89)
+            .filter(|v| *v == 99)
+            .max_scan(10)
+            .page(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(res.data, vec![99]);
+
+        let keys = Query::over(&TEST_MAP)
+            .prefix(1)
+            .filter(|v| *v > 90)
+            .max_scan(10)
+            .keys(deps.as_ref().storage)
+            .unwrap();
+        assert!(keys.data.is_empty());
+        assert_eq!(keys.next, Some(19));
+
+        let count = Query::over(&TEST_MAP)
+            .prefix(1)
+            .filter(|v| *v > 90)
+            .max_scan(40)
+            .count(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(count, (0, Some(79)));
+
+        let count = Query::over(&TEST_MAP)
+            .prefix(1)
+            .after(79)
+            .filter(|v| *v > 90)
+            .max_scan(40)
+            .count(deps.as_ref().storage)
+            .unwrap();
+        assert_eq!(count, (5, None));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::error::deserialize_key;
+use crate::{namespace, raw_range, Map, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_std::{from_json, Binary, Order, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+use sha2::{Digest, Sha256};
+
+pub type DefaultChecksumPage<K> = ChecksumPage<50, K>;
+
+/// Page meant for full exports, every response carries a SHA-256 chained from the
+/// `checksum` of the previous page over the raw key/value bytes it returned
+#[cw_serde]
+pub struct ChecksumPage<const LIMIT: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    /// Checksum returned alongside the previous page, empty on the first page
+    pub checksum: Option<Binary>,
+}
+
+#[cw_serde]
+pub struct ChecksumNextPage<D, K> {
+    pub data: Vec<D>,
+    pub next: Option<K>,
+    pub qty: usize,
+    pub checksum: Binary,
+}
+
+/// Chains `previous` with the given raw entries, each entry is hashed as its big endian
+/// `u32` lengths followed by the bytes so indexers can reproduce it off-chain
+pub fn chain_checksum<'e>(
+    previous: &[u8],
+    entries: impl IntoIterator<Item = (&'e [u8], &'e [u8])>,
+) -> Binary {
+    let mut hasher = Sha256::new();
+    hasher.update(previous);
+    for (key, value) in entries {
+        hasher.update((key.len() as u32).to_be_bytes());
+        hasher.update(key);
+        hasher.update((value.len() as u32).to_be_bytes());
+        hasher.update(value);
+    }
+    hasher.finalize().to_vec().into()
+}
+
+impl<const LIMIT: usize, Key> ChecksumPage<LIMIT, Key> {
+    pub fn into_checksum_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> Result<ChecksumNextPage<Data, Key::Output>, QueryError>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        Value: DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let records: Vec<_> = raw_range(
+            storage,
+            namespace(map),
+            &[],
+            self.start.map(|s| s.joined_key()),
+            Order::Ascending,
+        )
+        .take(self.qty.unwrap_or(LIMIT))
+        .collect();
+
+        let previous = self.checksum.unwrap_or_default();
+        let checksum = chain_checksum(
+            previous.as_slice(),
+            records.iter().map(|(k, v)| (k.as_slice(), v.as_slice())),
+        );
+
+        let mut data = vec![];
+        let mut end = None;
+
+        for (raw_key, raw_value) in records {
+            let key = deserialize_key::<Key>(raw_key)?;
+            let value: Value = from_json(&raw_value)?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(ChecksumNextPage {
+            data,
+            next: end,
+            qty: len,
+            checksum,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{chain_checksum, ChecksumPage, Map};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::to_json_vec;
+
+    #[test]
+    fn into_checksum_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..30 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let mut start = None;
+        let mut checksum = None;
+        let mut chained = vec![];
+        loop {
+            let query: ChecksumPage<8, u8> = ChecksumPage {
+                start,
+                qty: None,
+                checksum: checksum.clone(),
+            };
+            let res = query
+                .into_checksum_pagination(deps.as_ref().storage, &test_map, |k, v| (*k, v))
+                .unwrap();
+            if res.data.is_empty() {
+                break;
+            }
+            start = res.next;
+            checksum = Some(res.checksum);
+            chained.extend(res.data);
+        }
+
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = chained
+            .iter()
+            .map(|(k, v)| (vec![*k], to_json_vec(v).unwrap()))
+            .collect();
+        let pages: Vec<_> = entries.chunks(8).collect();
+        let expected = pages.iter().fold(vec![], |previous, page| {
+            chain_checksum(
+                &previous,
+                page.iter().map(|(k, v)| (k.as_slice(), v.as_slice())),
+            )
+            .to_vec()
+        });
+
+        assert_eq!(chained.len(), 30);
+        assert_eq!(checksum.unwrap().to_vec(), expected);
+
+        // Tampering with a single value changes the chain
+        test_map.save(deps.as_mut().storage, 3, &100).unwrap();
+        let query: ChecksumPage<8, u8> = ChecksumPage {
+            start: None,
+            qty: None,
+            checksum: None,
+        };
+        let res = query
+            .into_checksum_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_ne!(
+            res.checksum,
+            chain_checksum(
+                &[],
+                pages[0].iter().map(|(k, v)| (k.as_slice(), v.as_slice()))
+            )
+        );
+    }
+}
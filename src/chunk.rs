@@ -0,0 +1,145 @@
+use crate::{NextPage, PageError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{to_json_vec, StdError, StdResult};
+
+/// Part of a [`NextPage`] sent on its own, e.g. as an IBC packet. The cursor and counters
+/// of the page travel with the last chunk, together with its skipped entries
+#[cw_serde]
+pub struct PageChunk<D, K> {
+    pub index: u32,
+    pub total: u32,
+    pub data: Vec<D>,
+    pub next: Option<K>,
+    pub qty: usize,
+    pub scanned: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<PageError>,
+}
+
+impl<D: Serialize, K> NextPage<D, K> {
+    /// Splits the page into chunks whose json encoded items stay within `max_chunk_bytes`,
+    /// leave some headroom for the chunk fields and the packet envelope. An item above the
+    /// limit gets a chunk of its own, an empty page still yields a single chunk
+    pub fn into_chunks(self, max_chunk_bytes: usize) -> StdResult<Vec<PageChunk<D, K>>> {
+        let mut chunks: Vec<Vec<D>> = vec![vec![]];
+        let mut size = 0;
+
+        for item in self.data {
+            // Account for the separating comma
+            let item_size = to_json_vec(&item)?.len() + 1;
+            let current = chunks.last_mut().unwrap();
+            if !current.is_empty() && size + item_size > max_chunk_bytes {
+                chunks.push(vec![item]);
+                size = item_size;
+            } else {
+                current.push(item);
+                size += item_size;
+            }
+        }
+
+        let total = chunks.len() as u32;
+        let mut next = self.next;
+        let mut errors = self.errors;
+        Ok(chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, data)| {
+                let last = index as u32 + 1 == total;
+                PageChunk {
+                    index: index as u32,
+                    total,
+                    data,
+                    next: if last { next.take() } else { None },
+                    qty: if last { self.qty } else { 0 },
+                    scanned: if last { self.scanned } else { 0 },
+                    errors: if last {
+                        std::mem::take(&mut errors)
+                    } else {
+                        vec![]
+                    },
+                }
+            })
+            .collect())
+    }
+}
+
+impl<D, K> NextPage<D, K> {
+    /// Reassembles a page from all of its chunks, in any order
+    pub fn from_chunks(mut chunks: Vec<PageChunk<D, K>>) -> StdResult<Self> {
+        chunks.sort_by_key(|chunk| chunk.index);
+        let total = chunks.len() as u32;
+        if total == 0
+            || chunks
+                .iter()
+                .enumerate()
+                .any(|(i, chunk)| chunk.index != i as u32 || chunk.total != total)
+        {
+            return Err(StdError::generic_err("Missing or duplicated page chunks"));
+        }
+
+        let mut data = vec![];
+        let mut page = None;
+        for chunk in chunks {
+            data.extend(chunk.data);
+            if chunk.index + 1 == total {
+                page = Some((chunk.next, chunk.qty, chunk.scanned, chunk.errors));
+            }
+        }
+        let (next, qty, scanned, errors) = page.unwrap();
+
+        Ok(NextPage {
+            data,
+            next,
+            qty,
+            scanned,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::NextPage;
+
+    #[test]
+    fn into_chunks() {
+        let page = NextPage {
+            data: vec![
+                "aaaa".to_string(),
+                "bb".to_string(),
+                "cc".to_string(),
+                "d".repeat(20),
+            ],
+            next: Some(4u32),
+            qty: 4,
+            scanned: 4,
+            errors: vec![],
+        };
+
+        // Items take their json length plus a comma, e.g. 7 bytes for "aaaa"
+        let mut chunks = page.clone().into_chunks(12).unwrap();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].data, vec!["aaaa", "bb"]);
+        assert_eq!(chunks[0].next, None);
+        assert_eq!(chunks[1].data, vec!["cc"]);
+        assert_eq!(chunks[2].data, vec!["d".repeat(20)]);
+        assert_eq!((chunks[2].index, chunks[2].total), (2, 3));
+        assert_eq!(chunks[2].next, Some(4));
+
+        chunks.reverse();
+        assert_eq!(NextPage::from_chunks(chunks.clone()).unwrap(), page);
+
+        chunks.pop();
+        assert!(NextPage::from_chunks(chunks).is_err());
+
+        let empty: NextPage<u8, u8> = NextPage {
+            data: vec![],
+            next: None,
+            qty: 0,
+            scanned: 0,
+            errors: vec![],
+        };
+        assert_eq!(empty.into_chunks(12).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::NextPage;
+
+/// Calls `fetch` with each page's `next` until a page comes back without a cursor or
+/// with the cursor it was fetched from, returning every item in order. Empty pages that
+/// still move the cursor, e.g. filtered pages that hit their scan cap, are followed.
+/// Client side counterpart of `collect_all` for scripts draining a paginated endpoint
+pub fn drain_pages<D, K, E, Fetch>(mut fetch: Fetch) -> Result<Vec<D>, E>
+where
+    K: Clone + PartialEq,
+    Fetch: FnMut(Option<K>) -> Result<NextPage<D, K>, E>,
+{
+    let mut data = vec![];
+    let mut start = None;
+
+    loop {
+        let page = fetch(start.clone())?;
+        data.extend(page.data);
+
+        match page.next {
+            Some(next) if start.as_ref() != Some(&next) => start = Some(next),
+            _ => return Ok(data),
+        }
+    }
+}
+
+#[cfg(feature = "cw-orch")]
+pub use orch::*;
+
+#[cfg(feature = "cw-orch")]
+mod orch {
+    use crate::{drain_pages, NextPage};
+    use cosmwasm_schema::serde::de::DeserializeOwned;
+    use cosmwasm_schema::serde::Serialize;
+    use cw_orch::prelude::{CwEnv, CwOrchError, CwOrchQuery};
+    use std::fmt::Debug;
+
+    /// Drains a paginated endpoint of a cw-orch contract
+    ///
+    /// ```ignore
+    /// let accounts = contract.query_all::<AccountsResponse>(|start| QueryMsg::Accounts {
+    ///     page: Page { start, qty: None },
+    /// })?;
+    /// ```
+    pub trait QueryAll<Chain: CwEnv>: CwOrchQuery<Chain> {
+        fn query_all<Response>(
+            &self,
+            msg: impl Fn(Option<Response::Cursor>) -> Self::QueryMsg,
+        ) -> Result<Vec<Response::Item>, CwOrchError>
+        where
+            Response: PagedResponse;
+    }
+
+    impl<Chain: CwEnv, Contract: CwOrchQuery<Chain>> QueryAll<Chain> for Contract {
+        fn query_all<Response>(
+            &self,
+            msg: impl Fn(Option<Response::Cursor>) -> Self::QueryMsg,
+        ) -> Result<Vec<Response::Item>, CwOrchError>
+        where
+            Response: PagedResponse,
+        {
+            drain_pages(|start| {
+                self.query::<Response>(&msg(start))
+                    .map(PagedResponse::into_next_page)
+            })
+        }
+    }
+
+    /// Response types [`QueryAll`] can follow, implemented for [`NextPage`] so the
+    /// contract's response aliases can be passed directly
+    pub trait PagedResponse: Serialize + DeserializeOwned + Debug {
+        type Item;
+        type Cursor: Clone + PartialEq;
+        fn into_next_page(self) -> NextPage<Self::Item, Self::Cursor>;
+    }
+
+    impl<D, K> PagedResponse for NextPage<D, K>
+    where
+        D: Serialize + DeserializeOwned + Debug,
+        K: Serialize + DeserializeOwned + Debug + Clone + PartialEq,
+    {
+        type Item = D;
+        type Cursor = K;
+
+        fn into_next_page(self) -> NextPage<D, K> {
+            self
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        drain_pages, FilterOp, FilterPage, FilterValue, Map, NextPage, Page, PaginatedQuery,
+        ValueFilter,
+    };
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::StdResult;
+
+    #[test]
+    fn drain() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..10 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let mut calls = 0;
+        let data = drain_pages(|start| -> StdResult<NextPage<u8, u8>> {
+            calls += 1;
+            let page: Page<4, u8> = Page { start, qty: None };
+            page.into_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+        })
+        .unwrap();
+        assert_eq!(data, (0..10).collect::<Vec<_>>());
+        // Pages keep their cursor, the loop ends on the empty page after the last entry
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn drain_sparse_filter() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, i64> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map
+                .save(deps.as_mut().storage, i, &(i as i64))
+                .unwrap();
+        }
+
+        let mut calls = 0;
+        let data = drain_pages(|start| -> StdResult<NextPage<u8, u8>> {
+            calls += 1;
+            let page: FilterPage<5, 10, u8> = FilterPage {
+                start,
+                qty: None,
+                filter: Some(ValueFilter {
+                    field: "amount".to_string(),
+                    op: FilterOp::Eq,
+                    value: FilterValue::Int(95),
+                }),
+                max_scan: None,
+            };
+            page.into_filtered_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                |_, v| Some(FilterValue::Int(*v)),
+                |k, _| *k,
+            )
+        })
+        .unwrap();
+        // The empty pages before the match still move the cursor and are followed
+        assert_eq!(data, vec![95]);
+        assert_eq!(calls, 11);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Map, NextPage, Page, PaginatedQuery};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+
+/// Runs `page` and the pages following it until the map is exhausted or `max_pages` were
+/// read, flattening them into one page. `next` is only set when the cap stopped the loop,
+/// meant for genesis exports and sudo handlers where a single large read is acceptable
+pub fn collect_all<'a, const LIMIT: usize, Key, Value, Data, Function>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, Key, Value>,
+    page: Page<LIMIT, Key>,
+    transform: Function,
+    max_pages: usize,
+) -> StdResult<NextPage<Data, Key>>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize<Output = Key> + Clone + 'static,
+    Value: Serialize + DeserializeOwned,
+    Function: FnOnce(&Key, Value) -> Data + Copy,
+{
+    let limit = page.qty.unwrap_or(LIMIT);
+    let mut start = page.start;
+    let mut data = vec![];
+    let mut scanned = 0;
+
+    for _ in 0..max_pages {
+        let current: Page<LIMIT, Key> = Page {
+            start: start.take(),
+            qty: page.qty,
+        };
+        let res = current.into_pagination(storage, map, transform)?;
+        let exhausted = res.qty < limit;
+        scanned += res.scanned;
+        data.extend(res.data);
+
+        match res.next {
+            Some(next) if !exhausted => start = Some(next),
+            _ => {
+                let len = data.len();
+                return Ok(NextPage {
+                    data,
+                    next: None,
+                    qty: len,
+                    scanned,
+                    errors: vec![],
+                });
+            }
+        }
+    }
+
+    let len = data.len();
+    Ok(NextPage {
+        data,
+        next: start,
+        qty: len,
+        scanned,
+        errors: vec![],
+    })
+}
+
+/// Builds a page out of any key/value iterator, e.g. over a cache or a storage wrapper
+/// that doesn't expose a `Map`. The iterator must already start after the cursor, at most
+/// `limit` items are read and `cursor` turns the key of the last one into `next`
+pub fn paginate_iter<K, V, C, I, F>(iter: I, limit: usize, cursor: F) -> StdResult<NextPage<V, C>>
+where
+    I: IntoIterator<Item = StdResult<(K, V)>>,
+    F: Fn(&K) -> C,
+{
+    let mut data = vec![];
+    let mut end = None;
+
+    for item in iter.into_iter().take(limit) {
+        let (key, value) = item?;
+        data.push(value);
+        end = Some(key);
+    }
+
+    let len = data.len();
+    Ok(NextPage {
+        data,
+        next: end.as_ref().map(cursor),
+        qty: len,
+        scanned: len,
+        errors: vec![],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{collect_all, paginate_iter, Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::StdError;
+    use std::collections::BTreeMap;
+    use std::ops::Bound::{Excluded, Unbounded};
+
+    #[test]
+    fn collect_all_pages() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+
+        for i in 0..25 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let page: Page<10, u32> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = collect_all(deps.as_ref().storage, &test_map, page, |_, v| v, 10).unwrap();
+        assert_eq!(res.data, (0..25).collect::<Vec<_>>());
+        assert_eq!(res.next, None);
+
+        let page: Page<10, u32> = Page {
+            start: Some(2),
+            qty: Some(5),
+        };
+        let res = collect_all(deps.as_ref().storage, &test_map, page, |k, _| *k, 2).unwrap();
+        assert_eq!(res.data, (3..13).collect::<Vec<_>>());
+        assert_eq!(res.next, Some(12));
+    }
+
+    #[test]
+    fn paginate_iter_cache() {
+        let cache: BTreeMap<String, u32> = (0..10).map(|i| (format!("key-{}", i), i)).collect();
+        let after = "key-3".to_string();
+
+        let iter = cache
+            .range::<String, _>((Excluded(&after), Unbounded))
+            .map(|(k, v)| Ok((k, *v)));
+        let res = paginate_iter(iter, 4, |k| k.to_string()).unwrap();
+        assert_eq!(res.data, vec![4, 5, 6, 7]);
+        assert_eq!(res.next, Some("key-7".to_string()));
+
+        let iter = vec![Ok((1u8, 1u8)), Err(StdError::generic_err("corrupt"))];
+        assert!(paginate_iter(iter, 4, |k| *k).is_err());
+    }
+}
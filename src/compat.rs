@@ -0,0 +1,40 @@
+pub use cw_storage_plus::MultiIndex;
+#[cfg(not(feature = "cosmwasm_2"))]
+pub use cw_storage_plus::{Deque, IndexedMap, Item, Map, SnapshotMap};
+
+/// `cw-storage-plus` 2.x dropped the namespace lifetime, the alias keeps the
+/// signatures shared between both versions
+#[cfg(feature = "cosmwasm_2")]
+pub type Map<'a, K, V> = cw_storage_plus::Map<K, V>;
+#[cfg(feature = "cosmwasm_2")]
+pub type Item<'a, T> = cw_storage_plus::Item<T>;
+#[cfg(feature = "cosmwasm_2")]
+pub type Deque<'a, T> = cw_storage_plus::Deque<T>;
+#[cfg(feature = "cosmwasm_2")]
+pub type IndexedMap<'a, K, V, I> = cw_storage_plus::IndexedMap<K, V, I>;
+#[cfg(feature = "cosmwasm_2")]
+pub type SnapshotMap<'a, K, V> = cw_storage_plus::SnapshotMap<K, V>;
+
+/// Raw namespace the map was created with
+#[cfg(not(feature = "cosmwasm_2"))]
+pub(crate) fn namespace<'a, K, V>(map: &Map<'a, K, V>) -> &'a [u8] {
+    map.namespace()
+}
+
+/// Raw namespace the map was created with
+#[cfg(feature = "cosmwasm_2")]
+pub(crate) fn namespace<'m, K, V>(map: &'m Map<'_, K, V>) -> &'m [u8] {
+    map.namespace_bytes()
+}
+
+/// Map under a namespace built at runtime, e.g. `format!("orders:{owner}")`
+#[cfg(not(feature = "cosmwasm_2"))]
+pub fn dynamic_map<K, V>(namespace: &str) -> Map<'_, K, V> {
+    Map::new(namespace)
+}
+
+/// Map under a namespace built at runtime, e.g. `format!("orders:{owner}")`
+#[cfg(feature = "cosmwasm_2")]
+pub fn dynamic_map<K, V>(namespace: &str) -> Map<'_, K, V> {
+    Map::new_dyn(namespace.to_string())
+}
@@ -0,0 +1,106 @@
+use crate::{NextPage, PageError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_json, Binary, StdError, StdResult};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// [`crate::PageResponse`] whose json encoded `data` is zlib compressed when `compressed`
+/// is set, for maps holding large blobs
+#[cw_serde]
+pub struct CompressedPageResponse {
+    pub data: Binary,
+    pub compressed: bool,
+    pub next: Option<Binary>,
+    pub qty: usize,
+    #[serde(default)]
+    pub scanned: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<PageError>,
+}
+
+impl<D: Serialize, K: Serialize> NextPage<D, K> {
+    /// Same as `into_binary_response` but compresses the data once its json encoding is
+    /// above `threshold` bytes and the compressed form is actually smaller
+    pub fn into_compressed_response(self, threshold: usize) -> StdResult<CompressedPageResponse> {
+        let response = self.into_binary_response()?;
+        let mut data = response.data;
+        let mut compressed = false;
+
+        if data.len() > threshold {
+            let deflated = deflate(&data).map_err(|e| StdError::generic_err(e.to_string()))?;
+            if deflated.len() < data.len() {
+                data = deflated.into();
+                compressed = true;
+            }
+        }
+
+        Ok(CompressedPageResponse {
+            data,
+            compressed,
+            next: response.next,
+            qty: response.qty,
+            scanned: response.scanned,
+            errors: response.errors,
+        })
+    }
+}
+
+fn deflate(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+impl CompressedPageResponse {
+    /// Json encoded items, inflated if needed
+    pub fn decompressed_data(&self) -> StdResult<Binary> {
+        if !self.compressed {
+            return Ok(self.data.clone());
+        }
+
+        let mut data = vec![];
+        ZlibDecoder::new(self.data.as_slice())
+            .read_to_end(&mut data)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+        Ok(data.into())
+    }
+
+    pub fn into_page<D: DeserializeOwned, K: DeserializeOwned>(self) -> StdResult<NextPage<D, K>> {
+        Ok(NextPage {
+            data: from_json(self.decompressed_data()?)?,
+            next: self.next.map(from_json).transpose()?,
+            qty: self.qty,
+            scanned: self.scanned,
+            errors: self.errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::NextPage;
+
+    #[test]
+    fn into_compressed_response() {
+        let page = NextPage {
+            data: vec!["blob".repeat(100); 10],
+            next: Some(10u32),
+            qty: 10,
+            scanned: 10,
+            errors: vec![],
+        };
+
+        let res = page.clone().into_compressed_response(1024).unwrap();
+        assert!(res.compressed);
+        assert!(res.data.len() < 1024);
+        assert_eq!(res.into_page::<String, u32>().unwrap(), page);
+
+        let res = page.clone().into_compressed_response(8 * 1024).unwrap();
+        assert!(!res.compressed);
+        assert_eq!(res.into_page::<String, u32>().unwrap(), page);
+    }
+}
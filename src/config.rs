@@ -0,0 +1,163 @@
+use crate::error::validate_qty;
+use crate::{Page, QueryError, RangePage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Order;
+
+/// Iteration order as stored in contract state, converts into [`Order`]
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum PageOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl From<PageOrder> for Order {
+    fn from(order: PageOrder) -> Self {
+        match order {
+            PageOrder::Ascending => Order::Ascending,
+            PageOrder::Descending => Order::Descending,
+        }
+    }
+}
+
+/// Page limits tunable at runtime, usually kept in an `Item` and updated through an
+/// execute message so operators don't need to recompile the `LIMIT` const generic
+#[cw_serde]
+pub struct PageConfig {
+    pub max_limit: usize,
+    pub default_limit: usize,
+    pub default_order: PageOrder,
+}
+
+impl PageConfig {
+    /// Rejects configs whose default limit is zero or above the max
+    pub fn validate(&self) -> Result<(), QueryError> {
+        validate_qty(Some(self.default_limit), self.max_limit)
+    }
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Checks `qty` against the configured max and fills in the configured default,
+    /// taking the place of `LIMIT`. `Page` carries no order of its own, so it resolves
+    /// into a [`RangePage`] walking the map in the configured default order
+    pub fn resolve(self, config: &PageConfig) -> Result<RangePage<LIMIT, Key>, QueryError> {
+        validate_qty(self.qty, config.max_limit)?;
+        Ok(RangePage {
+            start: self.start,
+            end: None,
+            inclusive: false,
+            order: config.default_order,
+            qty: Some(self.qty.unwrap_or(config.default_limit)),
+        })
+    }
+}
+
+/// Compile time page sizes of an endpoint, declared once on a marker type and applied
+/// with [`Page::clamp`]. `Page<{ <Balances as Limits>::MAX }, Addr>` keeps the const
+/// generic in sync with the profile
+pub trait Limits {
+    const DEFAULT: usize;
+    const MAX: usize;
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Fills in the default of `L` and clamps `qty` between one and its max. Unlike
+    /// [`Page::validate`], a zero or oversized `qty` is corrected instead of rejected
+    pub fn clamp<L: Limits>(self) -> Self {
+        Page {
+            start: self.start,
+            qty: Some(self.qty.unwrap_or(L::DEFAULT).clamp(1, L::MAX.max(1))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Item, Limits, Map, Page, PageConfig, PageOrder, PaginatedQuery, QueryError};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn resolve() {
+        let mut deps = mock_dependencies();
+        let config: Item<PageConfig> = Item::new("page_config");
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let stored = PageConfig {
+            max_limit: 30,
+            default_limit: 5,
+            default_order: PageOrder::Ascending,
+        };
+        stored.validate().unwrap();
+        config.save(deps.as_mut().storage, &stored).unwrap();
+        let loaded = config.load(deps.as_ref().storage).unwrap();
+
+        let query: Page<50, u8> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .resolve(&loaded)
+            .unwrap()
+            .into_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.qty, 5);
+
+        let query: Page<50, u8> = Page {
+            start: None,
+            qty: Some(40),
+        };
+        assert_eq!(
+            query.resolve(&loaded).unwrap_err(),
+            QueryError::LimitExceeded {
+                requested: 40,
+                max: 30
+            }
+        );
+
+        // Pages follow the configured order
+        let descending = PageConfig {
+            default_order: PageOrder::Descending,
+            ..loaded
+        };
+        let query: Page<50, u8> = Page {
+            start: Some(50),
+            qty: Some(3),
+        };
+        let res = query
+            .resolve(&descending)
+            .unwrap()
+            .into_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![49, 48, 47]);
+        assert_eq!(res.next, Some(47));
+
+        let invalid = PageConfig {
+            max_limit: 10,
+            default_limit: 20,
+            default_order: PageOrder::Descending,
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    struct Balances;
+
+    impl Limits for Balances {
+        const DEFAULT: usize = 5;
+        const MAX: usize = 20;
+    }
+
+    #[test]
+    fn clamp() {
+        let page = |qty| Page::<{ Balances::MAX }, u8> { start: None, qty }.clamp::<Balances>();
+        assert_eq!(page(None).qty, Some(5));
+        assert_eq!(page(Some(8)).qty, Some(8));
+        assert_eq!(page(Some(50)).qty, Some(20));
+        assert_eq!(page(Some(0)).qty, Some(1));
+        assert!(page(Some(50)).validate().is_ok());
+    }
+}
@@ -0,0 +1,344 @@
+use crate::{dynamic_map, Deque, Map, NextPage, Page};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+/// Position a [`Page`] resumes from within `Source`, decoupling pagination from storage keys
+/// so offsets, `Deque` indexes or synthetic composite cursors can drive it as well
+pub trait PageCursor<Source: ?Sized>: Sized {
+    type Item;
+
+    /// Iterates `source` right after `start`, or from the beginning when there is none
+    fn resume<'a>(
+        start: Option<Self>,
+        storage: &'a dyn Storage,
+        source: &'a Source,
+    ) -> Box<dyn Iterator<Item = StdResult<(Self, Self::Item)>> + 'a>;
+}
+
+impl<'m, K, V> PageCursor<Map<'m, K, V>> for K
+where
+    K: PrimaryKey<'m> + KeyDeserialize<Output = K> + 'static,
+    V: Serialize + DeserializeOwned + 'static,
+{
+    type Item = V;
+
+    fn resume<'a>(
+        start: Option<Self>,
+        storage: &'a dyn Storage,
+        source: &'a Map<'m, K, V>,
+    ) -> Box<dyn Iterator<Item = StdResult<(Self, Self::Item)>> + 'a> {
+        source.range(
+            storage,
+            start.map(|s| Bound::ExclusiveRaw(s.joined_key())),
+            None,
+            Order::Ascending,
+        )
+    }
+}
+
+/// Cursor counting entries from the start of the map, for clients that page by position.
+/// Resuming still reads every key before the offset, so offsets past [`Offset::MAX`] are
+/// rejected
+#[cw_serde]
+#[derive(Copy)]
+pub struct Offset(pub u64);
+
+impl Offset {
+    pub const MAX: u64 = 10_000;
+}
+
+impl<'m, K, V> PageCursor<Map<'m, K, V>> for Offset
+where
+    K: PrimaryKey<'m> + KeyDeserialize + 'static,
+    V: Serialize + DeserializeOwned + 'static,
+{
+    type Item = (K::Output, V);
+
+    fn resume<'a>(
+        start: Option<Self>,
+        storage: &'a dyn Storage,
+        source: &'a Map<'m, K, V>,
+    ) -> Box<dyn Iterator<Item = StdResult<(Self, Self::Item)>> + 'a> {
+        let skip = start.map(|s| s.0.saturating_add(1)).unwrap_or(0);
+        if skip > Offset::MAX {
+            return Box::new(std::iter::once(Err(StdError::generic_err(
+                "Offset cursor is above the maximum offset",
+            ))));
+        }
+
+        // Walk the skipped entries by key only and resume the range right after them
+        let after = match skip.checked_sub(1) {
+            Some(last) => match source
+                .keys_raw(storage, None, None, Order::Ascending)
+                .nth(last as usize)
+            {
+                Some(raw_key) => Some(Bound::ExclusiveRaw(raw_key)),
+                None => return Box::new(std::iter::empty()),
+            },
+            None => None,
+        };
+        Box::new(
+            source
+                .range(storage, after, None, Order::Ascending)
+                .zip(skip..)
+                .map(|(item, i)| item.map(|entry| (Offset(i), entry))),
+        )
+    }
+}
+
+impl<'m, T> PageCursor<Deque<'m, T>> for u32
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    type Item = T;
+
+    fn resume<'a>(
+        start: Option<Self>,
+        storage: &'a dyn Storage,
+        source: &'a Deque<'m, T>,
+    ) -> Box<dyn Iterator<Item = StdResult<(Self, Self::Item)>> + 'a> {
+        let first = start.map(|s| s.saturating_add(1)).unwrap_or(0);
+        let len = match source.len(storage) {
+            Ok(len) => len,
+            Err(e) => return Box::new(std::iter::once(Err(e))),
+        };
+        Box::new((first..len).map(move |index| {
+            source
+                .get(storage, index)?
+                .map(|item| (index, item))
+                .ok_or_else(|| StdError::not_found(std::any::type_name::<T>()))
+        }))
+    }
+}
+
+impl<const LIMIT: usize, Cursor> Page<LIMIT, Cursor> {
+    /// Paginates any source the cursor knows how to resume, the returned cursor is the
+    /// last returned position
+    pub fn into_cursor_pagination<Source, Data, Function>(
+        self,
+        storage: &dyn Storage,
+        source: &Source,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Cursor>>
+    where
+        Source: ?Sized,
+        Cursor: PageCursor<Source>,
+        Function: FnOnce(&Cursor, Cursor::Item) -> Data + Copy,
+    {
+        let range = Cursor::resume(self.start, storage, source).take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for item in range {
+            let (cursor, item) = item?;
+            data.push(transform(&cursor, item));
+            end = Some(cursor);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+/// Continuation cursors persisted on-chain under a label, so batch jobs spanning
+/// several transactions don't have to thread the cursor through their messages
+pub struct SavedCursor<'a, K> {
+    cursors: Map<'a, String, K>,
+    // `Map` has no lifetime under cosmwasm_2
+    namespace: PhantomData<&'a ()>,
+}
+
+impl<'a, K> SavedCursor<'a, K>
+where
+    K: Serialize + DeserializeOwned,
+{
+    pub const fn new(namespace: &'static str) -> Self {
+        SavedCursor {
+            cursors: Map::new(namespace),
+            namespace: PhantomData,
+        }
+    }
+
+    /// Cursors under a namespace built at runtime, see [`dynamic_map`]
+    pub fn dynamic(namespace: &'a str) -> Self {
+        SavedCursor {
+            cursors: dynamic_map(namespace),
+            namespace: PhantomData,
+        }
+    }
+
+    pub fn load(&self, storage: &dyn Storage, label: &str) -> StdResult<Option<K>> {
+        self.cursors.may_load(storage, label.to_string())
+    }
+
+    /// Page resuming from the saved cursor, starts from the beginning if there is none
+    pub fn page<const LIMIT: usize>(
+        &self,
+        storage: &dyn Storage,
+        label: &str,
+        qty: Option<usize>,
+    ) -> StdResult<Page<LIMIT, K>> {
+        Ok(Page {
+            start: self.load(storage, label)?,
+            qty,
+        })
+    }
+
+    /// Stores the cursor returned by the last page, a `None` cursor means the job
+    /// went through every entry so the label is cleared. Returns whether there is more to process
+    pub fn advance(
+        &self,
+        storage: &mut dyn Storage,
+        label: &str,
+        next: Option<K>,
+    ) -> StdResult<bool> {
+        match next {
+            Some(next) => {
+                self.cursors.save(storage, label.to_string(), &next)?;
+                Ok(true)
+            }
+            None => {
+                self.clear(storage, label);
+                Ok(false)
+            }
+        }
+    }
+
+    pub fn clear(&self, storage: &mut dyn Storage, label: &str) {
+        self.cursors.remove(storage, label.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Deque, Map, Offset, Page, PaginatedMutation, SavedCursor};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_cursor_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+        let test_deque: Deque<String> = Deque::new("test_deque");
+
+        for i in 0..30 {
+            test_map.save(deps.as_mut().storage, i * 2, &i).unwrap();
+            test_deque
+                .push_back(deps.as_mut().storage, &format!("item-{}", i))
+                .unwrap();
+        }
+
+        let page: Page<5, u8> = Page {
+            start: Some(9),
+            qty: None,
+        };
+        let res = page
+            .into_cursor_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![10, 12, 14, 16, 18]);
+        assert_eq!(res.next, Some(18));
+
+        let page: Page<5, Offset> = Page {
+            start: Some(Offset(9)),
+            qty: Some(3),
+        };
+        let res = page
+            .into_cursor_pagination(deps.as_ref().storage, &test_map, |_, (k, _)| k)
+            .unwrap();
+        assert_eq!(res.data, vec![20, 22, 24]);
+        assert_eq!(res.next, Some(Offset(12)));
+
+        let page: Page<5, Offset> = Page {
+            start: Some(Offset(29)),
+            qty: None,
+        };
+        let res = page
+            .into_cursor_pagination(deps.as_ref().storage, &test_map, |_, (k, _)| k)
+            .unwrap();
+        assert!(res.data.is_empty());
+
+        let page: Page<5, Offset> = Page {
+            start: Some(Offset(Offset::MAX)),
+            qty: None,
+        };
+        assert!(page
+            .into_cursor_pagination(deps.as_ref().storage, &test_map, |_, (k, _)| k)
+            .is_err());
+
+        let page: Page<5, u32> = Page {
+            start: Some(26),
+            qty: None,
+        };
+        let res = page
+            .into_cursor_pagination(deps.as_ref().storage, &test_deque, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["item-27", "item-28", "item-29"]);
+        assert_eq!(res.next, Some(29));
+    }
+
+    #[test]
+    fn saved_cursor() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+        let cursor: SavedCursor<u8> = SavedCursor::new("cursors");
+
+        for i in 0..25 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let mut batches = 0;
+        loop {
+            let page = cursor
+                .page::<10>(deps.as_ref().storage, "migrate", None)
+                .unwrap();
+            let res = page
+                .update(deps.as_mut().storage, &test_map, |_, v| Ok(Some(v + 100)))
+                .unwrap();
+            batches += 1;
+            if !cursor
+                .advance(deps.as_mut().storage, "migrate", res.next)
+                .unwrap()
+            {
+                break;
+            }
+            if batches == 1 {
+                assert_eq!(
+                    cursor.load(deps.as_ref().storage, "migrate").unwrap(),
+                    Some(9)
+                );
+                assert_eq!(cursor.load(deps.as_ref().storage, "other").unwrap(), None);
+            }
+        }
+
+        assert_eq!(batches, 4);
+        assert_eq!(cursor.load(deps.as_ref().storage, "migrate").unwrap(), None);
+        assert_eq!(test_map.load(deps.as_ref().storage, 24).unwrap(), 124);
+
+        cursor
+            .advance(deps.as_mut().storage, "other", Some(3))
+            .unwrap();
+        cursor.clear(deps.as_mut().storage, "other");
+        assert_eq!(cursor.load(deps.as_ref().storage, "other").unwrap(), None);
+
+        // Cursors scoped under a runtime namespace don't see each other
+        let namespace = format!("cursors:{}", "alice");
+        let scoped: SavedCursor<u8> = SavedCursor::dynamic(&namespace);
+        scoped
+            .advance(deps.as_mut().storage, "migrate", Some(7))
+            .unwrap();
+        assert_eq!(
+            scoped.load(deps.as_ref().storage, "migrate").unwrap(),
+            Some(7)
+        );
+        assert_eq!(cursor.load(deps.as_ref().storage, "migrate").unwrap(), None);
+    }
+}
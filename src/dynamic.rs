@@ -0,0 +1,143 @@
+use crate::{Map, PaginatedQuery, QueryError};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_json, to_json_binary, Binary, Storage};
+use std::marker::PhantomData;
+
+/// Object safe counterpart of [`PaginatedQuery`], the page comes in and the result goes
+/// out json encoded so queries over different maps and page types can share a
+/// `Vec<Box<dyn DynPaginatedQuery>>`, e.g. in a generic admin query router
+pub trait DynPaginatedQuery<'a> {
+    /// Name the query is routed by
+    fn name(&self) -> &str;
+
+    fn paginate(&self, storage: &'a dyn Storage, page: &[u8]) -> Result<Binary, QueryError>;
+}
+
+/// Binds a map and a transform to the page type `Page` expected from clients
+pub struct DynQuery<'a, Page, Key, Value, Data, Function> {
+    name: String,
+    map: Map<'a, Key, Value>,
+    transform: Function,
+    // `Map` has no lifetime under cosmwasm_2
+    page: PhantomData<fn() -> (&'a (), Page, Data)>,
+}
+
+impl<'a, Page, Key, Value, Data, Function> DynQuery<'a, Page, Key, Value, Data, Function>
+where
+    Page: PaginatedQuery<'a, Key, Value, Data> + DeserializeOwned + 'a,
+    Page::POutput: Serialize,
+    Key: 'a,
+    Value: 'a,
+    Data: 'a,
+    Function: FnOnce(&Page::FuncKey, Value) -> Data + Copy + 'a,
+{
+    pub fn new(name: impl Into<String>, map: Map<'a, Key, Value>, transform: Function) -> Self {
+        DynQuery {
+            name: name.into(),
+            map,
+            transform,
+            page: PhantomData,
+        }
+    }
+
+    pub fn boxed(self) -> Box<dyn DynPaginatedQuery<'a> + 'a> {
+        Box::new(self)
+    }
+}
+
+impl<'a, Page, Key, Value, Data, Function> DynPaginatedQuery<'a>
+    for DynQuery<'a, Page, Key, Value, Data, Function>
+where
+    Page: PaginatedQuery<'a, Key, Value, Data> + DeserializeOwned,
+    Page::POutput: Serialize,
+    Function: FnOnce(&Page::FuncKey, Value) -> Data + Copy,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn paginate(&self, storage: &'a dyn Storage, page: &[u8]) -> Result<Binary, QueryError> {
+        let page: Page = from_json(page)?;
+        let res = page.into_pagination(storage, &self.map, self.transform)?;
+        Ok(to_json_binary(&res)?)
+    }
+}
+
+/// Runs the page against the query registered as `name`
+pub fn dispatch_page<'a>(
+    queries: &[Box<dyn DynPaginatedQuery<'a> + 'a>],
+    storage: &'a dyn Storage,
+    name: &str,
+    page: &[u8],
+) -> Result<Binary, QueryError> {
+    queries
+        .iter()
+        .find(|query| query.name() == name)
+        .ok_or_else(|| QueryError::UnknownMap {
+            name: name.to_string(),
+        })?
+        .paginate(storage, page)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{dispatch_page, DynQuery, LatestPage, Map, NextPage, Page, QueryError};
+    use cosmwasm_std::from_json;
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn dispatch() {
+        let mut deps = mock_dependencies();
+        let balances: Map<String, u128> = Map::new("balances");
+        let events: Map<u64, String> = Map::new("events");
+
+        for (i, addr) in ["alice", "bob", "carol"].iter().enumerate() {
+            balances
+                .save(deps.as_mut().storage, addr.to_string(), &(i as u128 * 100))
+                .unwrap();
+        }
+        for height in 0..5 {
+            events
+                .save(deps.as_mut().storage, height, &format!("event-{}", height))
+                .unwrap();
+        }
+
+        let queries = vec![
+            DynQuery::<Page<10, String>, _, _, _, _>::new("balances", balances, |_, v| v).boxed(),
+            DynQuery::<LatestPage<10, u64>, _, _, _, _>::new("events", events, |k, _| *k).boxed(),
+        ];
+
+        let res = dispatch_page(
+            &queries,
+            deps.as_ref().storage,
+            "balances",
+            br#"{"start":"alice","qty":null}"#,
+        )
+        .unwrap();
+        let page: NextPage<u128, String> = from_json(res).unwrap();
+        assert_eq!(page.data, vec![100, 200]);
+        assert_eq!(page.next, Some("carol".to_string()));
+
+        let res = dispatch_page(
+            &queries,
+            deps.as_ref().storage,
+            "events",
+            br#"{"before":null,"qty":2}"#,
+        )
+        .unwrap();
+        let page: NextPage<u64, u64> = from_json(res).unwrap();
+        assert_eq!(page.data, vec![4, 3]);
+
+        assert_eq!(
+            dispatch_page(&queries, deps.as_ref().storage, "orders", b"{}").unwrap_err(),
+            QueryError::UnknownMap {
+                name: "orders".to_string()
+            }
+        );
+        assert!(matches!(
+            dispatch_page(&queries, deps.as_ref().storage, "events", b"[]").unwrap_err(),
+            QueryError::Std(_)
+        ));
+    }
+}
@@ -0,0 +1,92 @@
+use crate::QueryError;
+#[cfg(feature = "msgpack")]
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Binary, StdError, StdResult};
+use std::any::type_name;
+
+/// MessagePack encoded page or cursor, fields are written positionally
+/// which keeps continuation tokens well below their JSON size
+#[cfg(feature = "msgpack")]
+pub fn to_msgpack_binary<T: Serialize>(value: &T) -> StdResult<Binary> {
+    rmp_serde::to_vec(value)
+        .map(Binary::from)
+        .map_err(|e| StdError::serialize_err(type_name::<T>(), e))
+}
+
+#[cfg(feature = "msgpack")]
+pub fn from_msgpack<T: DeserializeOwned>(value: impl AsRef<[u8]>) -> Result<T, QueryError> {
+    rmp_serde::from_slice(value.as_ref())
+        .map_err(|e| QueryError::CursorDecode { msg: e.to_string() })
+}
+
+/// Borsh encoded page or cursor, the key type must implement the borsh traits as well
+#[cfg(feature = "borsh")]
+pub fn to_borsh_binary<T: borsh::BorshSerialize>(value: &T) -> StdResult<Binary> {
+    borsh::to_vec(value)
+        .map(Binary::from)
+        .map_err(|e| StdError::serialize_err(type_name::<T>(), e))
+}
+
+#[cfg(feature = "borsh")]
+pub fn from_borsh<T: borsh::BorshDeserialize>(value: impl AsRef<[u8]>) -> Result<T, QueryError> {
+    borsh::from_slice(value.as_ref()).map_err(|e| QueryError::CursorDecode { msg: e.to_string() })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{NextPage, Page};
+    use cosmwasm_std::to_json_vec;
+
+    #[test]
+    #[cfg(feature = "msgpack")]
+    fn msgpack_roundtrip() {
+        use crate::{from_msgpack, to_msgpack_binary};
+
+        let page: Page<20, String> = Page {
+            start: Some("string-010".to_string()),
+            qty: Some(5),
+        };
+        let encoded = to_msgpack_binary(&page).unwrap();
+        assert!(encoded.len() < to_json_vec(&page).unwrap().len());
+        assert_eq!(from_msgpack::<Page<20, String>>(&encoded).unwrap(), page);
+
+        let next = NextPage {
+            data: vec![1u8, 2, 3],
+            next: Some(3u8),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+        let encoded = to_msgpack_binary(&next).unwrap();
+        assert_eq!(from_msgpack::<NextPage<u8, u8>>(&encoded).unwrap(), next);
+        assert!(from_msgpack::<Page<20, String>>([0xc1]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "borsh")]
+    fn borsh_roundtrip() {
+        use crate::{from_borsh, to_borsh_binary};
+
+        let page: Page<20, (u64, String)> = Page {
+            start: Some((7, "string-010".to_string())),
+            qty: None,
+        };
+        let encoded = to_borsh_binary(&page).unwrap();
+        assert!(encoded.len() < to_json_vec(&page).unwrap().len());
+        assert_eq!(
+            from_borsh::<Page<20, (u64, String)>>(&encoded).unwrap(),
+            page
+        );
+
+        let next = NextPage {
+            data: vec!["a".to_string()],
+            next: Some(1u32),
+            qty: 1,
+            scanned: 1,
+            errors: vec![],
+        };
+        let encoded = to_borsh_binary(&next).unwrap();
+        assert_eq!(from_borsh::<NextPage<String, u32>>(&encoded).unwrap(), next);
+        assert!(from_borsh::<NextPage<String, u32>>([2]).is_err());
+    }
+}
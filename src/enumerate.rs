@@ -0,0 +1,78 @@
+use crate::{Map, NextPage, Page};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Same as `into_pagination` but passes the zero based position within the page to
+    /// the transform, e.g. for numbered listings. Ranks across pages are better served by
+    /// paging with an [`crate::Offset`] cursor, which carries the absolute position
+    pub fn into_enumerated_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(usize, &Key::Output, Value) -> Data + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for (index, item) in range.enumerate() {
+            let (key, value) = item?;
+            data.push(transform(index, &key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_enumerated_pagination() {
+        let mut deps = mock_dependencies();
+        let scores: Map<&str, u32> = Map::new("scores");
+
+        for (name, score) in [("alice", 30), ("bob", 10), ("carol", 20), ("dave", 5)] {
+            scores.save(deps.as_mut().storage, name, &score).unwrap();
+        }
+
+        let query: Page<2, &str> = Page {
+            start: Some("alice"),
+            qty: None,
+        };
+        let res = query
+            .into_enumerated_pagination(deps.as_ref().storage, &scores, |i, name, score| {
+                format!("{}. {} {}", i + 1, name, score)
+            })
+            .unwrap();
+        assert_eq!(res.data, vec!["1. bob 10", "2. carol 20"]);
+        assert_eq!(res.next, Some("carol".to_string()));
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{NextPage, PageError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_json, to_json_binary, Binary, StdResult};
+use std::collections::BTreeMap;
+
+/// Envelope version written by this release, bumped whenever an extension is added
+pub const PAGE_SCHEMA_VERSION: u8 = 1;
+
+/// [`NextPage`] tagged with the envelope `schema` it was written with. Fields added after
+/// the first version travel in `extensions`, so typed clients built against an older
+/// schema keep deserializing responses instead of failing on unknown fields
+#[cw_serde]
+pub struct PageEnvelope<D, K> {
+    pub schema: u8,
+    pub data: Vec<D>,
+    pub next: Option<K>,
+    pub qty: usize,
+    #[serde(default)]
+    pub scanned: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<PageError>,
+    /// Json encoded values by extension name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub extensions: BTreeMap<String, Binary>,
+}
+
+impl<D, K> NextPage<D, K> {
+    pub fn into_envelope(self) -> PageEnvelope<D, K> {
+        PageEnvelope {
+            schema: PAGE_SCHEMA_VERSION,
+            data: self.data,
+            next: self.next,
+            qty: self.qty,
+            scanned: self.scanned,
+            errors: self.errors,
+            extensions: BTreeMap::new(),
+        }
+    }
+}
+
+impl<D, K> PageEnvelope<D, K> {
+    pub fn with_extension(
+        mut self,
+        name: impl Into<String>,
+        value: &impl Serialize,
+    ) -> StdResult<Self> {
+        self.extensions.insert(name.into(), to_json_binary(value)?);
+        Ok(self)
+    }
+
+    /// Decoded extension, `None` when the page was written without it
+    pub fn extension<T: DeserializeOwned>(&self, name: &str) -> StdResult<Option<T>> {
+        self.extensions.get(name).map(from_json).transpose()
+    }
+
+    /// Drops the envelope, extensions unknown to the caller are discarded
+    pub fn into_next_page(self) -> NextPage<D, K> {
+        NextPage {
+            data: self.data,
+            next: self.next,
+            qty: self.qty,
+            scanned: self.scanned,
+            errors: self.errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{NextPage, PageEnvelope, PAGE_SCHEMA_VERSION};
+    use cosmwasm_std::{from_json, to_json_vec};
+
+    #[test]
+    fn into_envelope() {
+        let page = NextPage {
+            data: vec![1u8, 2, 3],
+            next: Some(3u8),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+
+        let envelope = page.clone().into_envelope();
+        assert_eq!(envelope.schema, PAGE_SCHEMA_VERSION);
+        assert_eq!(envelope.clone().into_next_page(), page);
+
+        // A client that doesn't know the `total` extension still reads the page
+        let json = to_json_vec(&envelope.with_extension("total", &42u64).unwrap()).unwrap();
+        let envelope: PageEnvelope<u8, u8> = from_json(json).unwrap();
+        assert_eq!(envelope.extension::<u64>("total").unwrap(), Some(42));
+        assert_eq!(envelope.extension::<u64>("prev").unwrap(), None);
+        assert_eq!(envelope.into_next_page(), page);
+    }
+}
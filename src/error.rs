@@ -0,0 +1,134 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Deserialize;
+use cosmwasm_std::{from_json, Binary, StdError};
+use cw_storage_plus::KeyDeserialize;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum QueryError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Could not decode cursor: {msg}")]
+    CursorDecode { msg: String },
+
+    #[error("Cursor was issued for key format version {found}, expected {expected}")]
+    CursorVersion { expected: u8, found: u8 },
+
+    #[error("Cursor was issued for {found} iteration, expected {expected}")]
+    CursorDirection { expected: String, found: String },
+
+    #[error("Cursor was issued at height {issued}, more than {max_age} blocks ago")]
+    CursorExpired { issued: u64, max_age: u64 },
+
+    #[error("Cursor signature doesn't match")]
+    CursorSignature {},
+
+    #[error("Requested an empty page")]
+    ZeroLimit {},
+
+    #[error("Requested {requested} entries but at most {max} are allowed")]
+    LimitExceeded { requested: usize, max: usize },
+
+    #[error("Could not deserialize stored key {raw}")]
+    KeyDeserialize { raw: Binary },
+
+    #[error("Unknown map {name}")]
+    UnknownMap { name: String },
+}
+
+impl From<QueryError> for StdError {
+    fn from(err: QueryError) -> Self {
+        match err {
+            QueryError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
+
+/// How a page reacts to entries whose key or value can't be deserialized
+#[cw_serde]
+#[derive(Copy, Default)]
+pub enum ErrorPolicy {
+    /// Fail the whole page
+    #[default]
+    Abort,
+    /// Leave the entry out and report it in [`crate::NextPage::errors`]
+    Skip,
+}
+
+/// Entry left out of a page under [`ErrorPolicy::Skip`]
+#[cw_serde]
+pub struct PageError {
+    pub key: Binary,
+    pub error: String,
+}
+
+/// Rejects empty pages and pages asking for more than `max` entries
+pub(crate) fn validate_qty(qty: Option<usize>, max: usize) -> Result<(), QueryError> {
+    match qty {
+        Some(0) => Err(QueryError::ZeroLimit {}),
+        Some(requested) if requested > max => Err(QueryError::LimitExceeded { requested, max }),
+        _ => Ok(()),
+    }
+}
+
+/// Parses a client provided page whose cursor is the `start` field. Only a malformed `start`
+/// is reported as [`QueryError::CursorDecode`], any other mistake such as a bad `qty` or an
+/// unknown field stays a parse error
+pub(crate) fn parse_page<T, Cursor>(value: &[u8]) -> Result<T, QueryError>
+where
+    T: DeserializeOwned,
+    Cursor: DeserializeOwned,
+{
+    from_json(value).map_err(|err| match from_json::<StartField<Cursor>>(value) {
+        Err(e) => QueryError::CursorDecode { msg: e.to_string() },
+        Ok(_) => err.into(),
+    })
+}
+
+/// Reads only the cursor of a page, other fields are ignored
+#[derive(Deserialize)]
+#[serde(crate = "cosmwasm_schema::serde")]
+#[allow(dead_code)]
+struct StartField<Cursor> {
+    #[serde(default)]
+    start: Option<Cursor>,
+}
+
+/// Deserializes a raw storage key, keeping the offending bytes in the error
+pub(crate) fn deserialize_key<K: KeyDeserialize>(raw: Vec<u8>) -> Result<K::Output, QueryError> {
+    K::from_slice(&raw).map_err(|_| QueryError::KeyDeserialize { raw: raw.into() })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::deserialize_key;
+    use crate::QueryError;
+    use cosmwasm_std::{Binary, StdError};
+
+    #[test]
+    fn into_std_error() {
+        let err: StdError = QueryError::LimitExceeded {
+            requested: 100,
+            max: 50,
+        }
+        .into();
+        assert_eq!(
+            err,
+            StdError::generic_err("Requested 100 entries but at most 50 are allowed")
+        );
+
+        let err: StdError = QueryError::Std(StdError::not_found("u8")).into();
+        assert_eq!(err, StdError::not_found("u8"));
+
+        assert_eq!(
+            deserialize_key::<u32>(vec![1, 2]),
+            Err(QueryError::KeyDeserialize {
+                raw: Binary::from(vec![1, 2])
+            })
+        );
+        assert_eq!(deserialize_key::<u32>(vec![0, 0, 0, 7]), Ok(7));
+    }
+}
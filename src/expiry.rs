@@ -0,0 +1,193 @@
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{BlockInfo, Order, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+/// Point in time after which a stored entry is no longer active,
+/// mirrors the shape of `cw_utils::Expiration`
+#[cw_serde]
+#[derive(Copy)]
+pub enum Expiry {
+    AtHeight(u64),
+    AtTime(Timestamp),
+    Never {},
+}
+
+impl Expiry {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        match self {
+            Expiry::AtHeight(height) => block.height >= *height,
+            Expiry::AtTime(time) => block.time >= *time,
+            Expiry::Never {} => false,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct Expirable<D> {
+    pub data: D,
+    pub expired: bool,
+}
+
+pub type DefaultExpiryPage<K> = ExpiryPage<50, 500, K>;
+
+/// Page skipping expired entries, scanning at most `MAX_SCAN` entries so a namespace
+/// full of expired entries can't exhaust the query gas
+#[cw_serde]
+pub struct ExpiryPage<const LIMIT: usize, const MAX_SCAN: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    /// Return expired entries flagged instead of skipping them
+    pub include_expired: Option<bool>,
+    pub max_scan: Option<usize>,
+}
+
+impl<const LIMIT: usize, const MAX_SCAN: usize, Key> ExpiryPage<LIMIT, MAX_SCAN, Key> {
+    pub fn into_expiry_pagination<'a, Value, Data, Expires, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        block: &BlockInfo,
+        expires: Expires,
+        transform: Function,
+    ) -> StdResult<NextPage<Expirable<Data>, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Expires: Fn(&Value) -> Expiry,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let include_expired = self.include_expired.unwrap_or(false);
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(max_scan);
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            if data.len() >= limit {
+                break;
+            }
+
+            let (key, value) = item?;
+            scanned += 1;
+            let expired = expires(&value).is_expired(block);
+            if !expired || include_expired {
+                data.push(Expirable {
+                    data: transform(&key, value),
+                    expired,
+                });
+            }
+
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Expiry, ExpiryPage, Map};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+
+    #[test]
+    fn into_expiry_pagination() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let test_map: Map<u8, Expiry> = Map::new("test_map");
+
+        for i in 0..100 {
+            let expiry = if i % 2 == 0 {
+                Expiry::AtHeight(env.block.height)
+            } else {
+                Expiry::AtTime(env.block.time.plus_seconds(10))
+            };
+            test_map.save(deps.as_mut().storage, i, &expiry).unwrap();
+        }
+
+        let query: ExpiryPage<20, 100, _> = ExpiryPage {
+            start: None,
+            qty: None,
+            include_expired: None,
+            max_scan: None,
+        };
+
+        let res = query
+            .into_expiry_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                &env.block,
+                |v| *v,
+                |k, _| *k,
+            )
+            .unwrap();
+
+        assert_eq!(res.qty, 20);
+        assert!(res.data.iter().all(|item| !item.expired));
+        assert_eq!(res.data.first().unwrap().data, 1);
+        assert_eq!(res.data.last().unwrap().data, 39);
+        assert_eq!(res.next, Some(39));
+
+        let query: ExpiryPage<20, 100, _> = ExpiryPage {
+            start: res.next,
+            qty: Some(4),
+            include_expired: Some(true),
+            max_scan: None,
+        };
+
+        let res = query
+            .into_expiry_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                &env.block,
+                |v| *v,
+                |k, _| *k,
+            )
+            .unwrap();
+
+        assert_eq!(res.qty, 4);
+        assert_eq!(res.next, Some(43));
+        assert!(res.data.first().unwrap().expired);
+        assert!(!res.data.get(1).unwrap().expired);
+
+        // Only expired entries within the scan cap, the cursor still advances
+        let query: ExpiryPage<20, 100, _> = ExpiryPage {
+            start: None,
+            qty: None,
+            include_expired: None,
+            max_scan: Some(1),
+        };
+        let res = query
+            .into_expiry_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                &env.block,
+                |v| *v,
+                |k, _| *k,
+            )
+            .unwrap();
+        assert!(res.data.is_empty());
+        assert_eq!(res.scanned, 1);
+        assert_eq!(res.next, Some(0));
+    }
+}
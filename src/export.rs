@@ -0,0 +1,176 @@
+use crate::error::validate_qty;
+use crate::{Map, NextPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Binary, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, PrimaryKey};
+
+pub type DefaultExportPlan = ExportPlan<16, 10_000>;
+pub type DefaultExportPage = ExportPage<50>;
+
+/// Raw key range, `start` inclusive and `end` exclusive. Open ends reach the edge of the map
+#[cw_serde]
+pub struct ExportRange {
+    pub start: Option<Binary>,
+    pub end: Option<Binary>,
+}
+
+/// Splits the key space of a map into up to `parts` contiguous [`ExportRange`]s of about
+/// the same size, so off-chain tools can export them in parallel through [`ExportPage`].
+/// Boundaries are sampled from the first `MAX_SCAN` keys, the last range stays open and
+/// holds the rest of larger maps
+#[cw_serde]
+pub struct ExportPlan<const MAX_PARTS: usize, const MAX_SCAN: usize> {
+    pub parts: usize,
+    pub max_scan: Option<usize>,
+}
+
+impl<const MAX_PARTS: usize, const MAX_SCAN: usize> ExportPlan<MAX_PARTS, MAX_SCAN> {
+    /// Ranges in key order, together they cover the whole map without overlapping
+    pub fn into_export_ranges<'a, Key, Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> Result<Vec<ExportRange>, QueryError>
+    where
+        Key: PrimaryKey<'a>,
+        Value: Serialize + DeserializeOwned,
+    {
+        validate_qty(Some(self.parts), MAX_PARTS)?;
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let keys: Vec<_> = map
+            .keys_raw(storage, None, None, Order::Ascending)
+            .take(max_scan)
+            .collect();
+        let parts = self.parts.min(keys.len()).max(1);
+
+        let mut ranges = Vec::with_capacity(parts);
+        let mut start: Option<Binary> = None;
+        for part in 1..parts {
+            let boundary: Binary = keys[part * keys.len() / parts].clone().into();
+            ranges.push(ExportRange {
+                start: start.replace(boundary.clone()),
+                end: Some(boundary),
+            });
+        }
+        ranges.push(ExportRange { start, end: None });
+
+        Ok(ranges)
+    }
+}
+
+/// Page over a single [`ExportRange`], the cursor is the raw key of the last entry
+#[cw_serde]
+pub struct ExportPage<const LIMIT: usize> {
+    pub range: ExportRange,
+    pub cursor: Option<Binary>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize> ExportPage<LIMIT> {
+    pub fn into_export_pagination<'a, Key, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Binary>>
+    where
+        Key: PrimaryKey<'a>,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Binary, Value) -> Data + Copy,
+    {
+        let min = match self.cursor {
+            Some(cursor) => Some(Bound::ExclusiveRaw(cursor.to_vec())),
+            None => self.range.start.map(|s| Bound::InclusiveRaw(s.to_vec())),
+        };
+        let max = self.range.end.map(|e| Bound::ExclusiveRaw(e.to_vec()));
+        let range = map
+            .range_raw(storage, min, max, Order::Ascending)
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for item in range {
+            let (raw_key, value) = item?;
+            let key: Binary = raw_key.into();
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ExportPage, ExportPlan, Map, QueryError};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_export_ranges() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+
+        for i in 0..10 {
+            test_map.save(deps.as_mut().storage, i, &(i * 10)).unwrap();
+        }
+
+        let plan: ExportPlan<4, 100> = ExportPlan {
+            parts: 3,
+            max_scan: None,
+        };
+        let ranges = plan
+            .into_export_ranges(deps.as_ref().storage, &test_map)
+            .unwrap();
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0].start, None);
+        assert_eq!(ranges[0].end, Some(3u32.to_be_bytes().into()));
+        assert_eq!(ranges[1].start, ranges[0].end);
+        assert_eq!(ranges[2].start, Some(6u32.to_be_bytes().into()));
+        assert_eq!(ranges[2].end, None);
+
+        // Every range paged to the end, in any order, exports the whole map once
+        let mut exported = vec![];
+        for range in ranges.into_iter().rev() {
+            let mut cursor = None;
+            loop {
+                let query: ExportPage<2> = ExportPage {
+                    range: range.clone(),
+                    cursor,
+                    qty: None,
+                };
+                let res = query
+                    .into_export_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+                    .unwrap();
+                if res.data.is_empty() {
+                    break;
+                }
+                exported.extend(res.data);
+                cursor = res.next;
+            }
+        }
+        exported.sort();
+        assert_eq!(exported, (0..10).map(|i| i * 10).collect::<Vec<_>>());
+
+        let plan: ExportPlan<4, 100> = ExportPlan {
+            parts: 5,
+            max_scan: None,
+        };
+        assert_eq!(
+            plan.into_export_ranges(deps.as_ref().storage, &test_map)
+                .unwrap_err(),
+            QueryError::LimitExceeded {
+                requested: 5,
+                max: 4
+            }
+        );
+    }
+}
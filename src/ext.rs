@@ -0,0 +1,108 @@
+use crate::{KeysIter, KeysQuery, Map, PaginatedQuery};
+use cosmwasm_std::{CustomQuery, Deps, StdResult};
+
+/// Exposes the pagination traits directly on the map so handlers don't have to
+/// import them or remember the argument order
+pub trait MapQueryExt<'a, Key, Value> {
+    fn paginate<C, Page, Data, Function>(
+        &self,
+        deps: Deps<'a, C>,
+        page: Page,
+        transform: Function,
+    ) -> StdResult<Page::POutput>
+    where
+        C: CustomQuery,
+        Page: PaginatedQuery<'a, Key, Value, Data>,
+        Function: FnOnce(&Page::FuncKey, Value) -> Data + Copy;
+
+    fn page_keys<C, Page>(&self, deps: Deps<'a, C>, page: Page) -> KeysIter<'a, Page::KOutput>
+    where
+        C: CustomQuery,
+        Page: KeysQuery<'a, Key, Value>;
+}
+
+impl<'a, 'm: 'a, Key, Value> MapQueryExt<'a, Key, Value> for Map<'m, Key, Value> {
+    fn paginate<C, Page, Data, Function>(
+        &self,
+        deps: Deps<'a, C>,
+        page: Page,
+        transform: Function,
+    ) -> StdResult<Page::POutput>
+    where
+        C: CustomQuery,
+        Page: PaginatedQuery<'a, Key, Value, Data>,
+        Function: FnOnce(&Page::FuncKey, Value) -> Data + Copy,
+    {
+        page.into_pagination(deps.storage, self, transform)
+    }
+
+    fn page_keys<C, Page>(&self, deps: Deps<'a, C>, page: Page) -> KeysIter<'a, Page::KOutput>
+    where
+        C: CustomQuery,
+        Page: KeysQuery<'a, Key, Value>,
+    {
+        page.keys(deps.storage, self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, MapQueryExt, Page, PrefixPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    const TEST_MAP: Map<'static, (u8, String), u8> = Map::new("TEST_MAP");
+
+    #[test]
+    fn paginate_on_map() {
+        let mut deps = mock_dependencies();
+
+        for i in 0..100 {
+            TEST_MAP
+                .save(
+                    deps.as_mut().storage,
+                    (i % 2, format!("string-{:0>3}", i)),
+                    &i,
+                )
+                .unwrap();
+        }
+
+        let page: Page<20, _> = Page {
+            start: None,
+            qty: Some(3),
+        };
+        let res = TEST_MAP.paginate(deps.as_ref(), page, |_, v| v).unwrap();
+        assert_eq!(res.data, vec![0, 2, 4]);
+
+        let page: PrefixPage<20, _, _, _> = PrefixPage {
+            prefix: 1,
+            start: None,
+            qty: Some(3),
+        };
+        let res = TEST_MAP.paginate(deps.as_ref(), page, |_, v| v).unwrap();
+        assert_eq!(res.data, vec![1, 3, 5]);
+        assert_eq!(res.next, Some("string-005".to_string()));
+
+        let page: PrefixPage<20, _, _, _> = PrefixPage {
+            prefix: 1,
+            start: None,
+            qty: Some(2),
+        };
+        let keys: Vec<String> = TEST_MAP
+            .page_keys(deps.as_ref(), page)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(keys, vec!["string-001", "string-003"]);
+
+        let page: PrefixPage<20, _, _, _> = PrefixPage {
+            prefix: 0,
+            start: Some("string-002".to_string()),
+            qty: Some(2),
+        };
+        let res = TEST_MAP
+            .page_keys(deps.as_ref(), page)
+            .collect_page()
+            .unwrap();
+        assert_eq!(res.data, vec!["string-004", "string-006"]);
+        assert_eq!(res.next, Some("string-006".to_string()));
+    }
+}
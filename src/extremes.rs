@@ -0,0 +1,109 @@
+use crate::Map;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+
+/// Lowest and highest entries of a prefix by a value derived metric, ties keep the first
+/// entry in key order. `next` is only set when `max_scan` cut the scan short, in which
+/// case the extremes only cover the entries up to it
+#[cw_serde]
+pub struct Extremes<K, V> {
+    pub min: Option<(K, V)>,
+    pub max: Option<(K, V)>,
+    pub scanned: usize,
+    pub next: Option<K>,
+}
+
+/// Scans up to `max_scan` entries under `prefix` for the ones with the lowest and highest
+/// `metric`, e.g. best bid and best ask, instead of over-fetching a page and reducing it
+/// client side
+pub fn prefix_extremes<'a, Key, Value, Metric, Function>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, Key, Value>,
+    prefix: Key::Prefix,
+    max_scan: usize,
+    metric: Function,
+) -> StdResult<Extremes<<Key::Suffix as KeyDeserialize>::Output, Value>>
+where
+    Key: PrimaryKey<'a>,
+    Key::Suffix: PrimaryKey<'a> + KeyDeserialize,
+    <Key::Suffix as KeyDeserialize>::Output: Clone + 'static,
+    Value: Serialize + DeserializeOwned + Clone,
+    Metric: PartialOrd + Clone,
+    Function: Fn(&Value) -> Metric,
+{
+    let range = map
+        .prefix(prefix)
+        .range(storage, None, None, Order::Ascending)
+        .take(max_scan);
+    let mut min = None;
+    let mut max = None;
+    let mut end = None;
+    let mut scanned = 0;
+
+    for item in range {
+        let (key, value) = item?;
+        let current = metric(&value);
+        let is_min = match &min {
+            None => true,
+            Some((lowest, _, _)) => current < *lowest,
+        };
+        let is_max = match &max {
+            None => true,
+            Some((highest, _, _)) => current > *highest,
+        };
+
+        if is_min {
+            min = Some((current.clone(), key.clone(), value.clone()));
+        }
+        if is_max {
+            max = Some((current, key.clone(), value));
+        }
+        scanned += 1;
+        end = Some(key);
+    }
+
+    Ok(Extremes {
+        min: min.map(|(_, key, value)| (key, value)),
+        max: max.map(|(_, key, value)| (key, value)),
+        scanned,
+        next: end.filter(|_| scanned == max_scan),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{prefix_extremes, Map};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn extremes() {
+        let mut deps = mock_dependencies();
+        let bids: Map<(&str, u64), u64> = Map::new("bids");
+
+        for (id, price) in [12u64, 9, 15, 9, 11, 20].into_iter().enumerate() {
+            bids.save(deps.as_mut().storage, ("atom", id as u64), &price)
+                .unwrap();
+        }
+        bids.save(deps.as_mut().storage, ("osmo", 0), &1).unwrap();
+
+        let res =
+            prefix_extremes(deps.as_ref().storage, &bids, "atom", 10, |price| *price).unwrap();
+        assert_eq!(res.min, Some((1, 9)));
+        assert_eq!(res.max, Some((5, 20)));
+        assert_eq!(res.scanned, 6);
+        assert_eq!(res.next, None);
+
+        let res = prefix_extremes(deps.as_ref().storage, &bids, "atom", 3, |price| *price).unwrap();
+        assert_eq!(res.min, Some((1, 9)));
+        assert_eq!(res.max, Some((2, 15)));
+        assert_eq!(res.next, Some(2));
+
+        let res =
+            prefix_extremes(deps.as_ref().storage, &bids, "juno", 10, |price| *price).unwrap();
+        assert_eq!(res.min, None);
+        assert_eq!(res.max, None);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+/// Field value extracted from a stored value by the contract
+#[cw_serde]
+pub enum FilterValue {
+    String(String),
+    Uint(Uint128),
+    Int(i64),
+    Bool(bool),
+}
+
+impl FilterValue {
+    fn compare(&self, other: &FilterValue) -> Option<Ordering> {
+        match (self, other) {
+            (FilterValue::String(a), FilterValue::String(b)) => Some(a.cmp(b)),
+            (FilterValue::Uint(a), FilterValue::Uint(b)) => Some(a.cmp(b)),
+            (FilterValue::Int(a), FilterValue::Int(b)) => Some(a.cmp(b)),
+            (FilterValue::Bool(a), FilterValue::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+}
+
+#[cw_serde]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Lt,
+    Contains,
+}
+
+/// Client provided predicate, the contract decides how `field` is projected out of its values
+#[cw_serde]
+pub struct ValueFilter {
+    pub field: String,
+    pub op: FilterOp,
+    pub value: FilterValue,
+}
+
+impl ValueFilter {
+    /// Checks the projected field, fields the contract does not know or of another type
+    /// than `value` never match, not even `Ne`
+    pub fn matches(&self, field: Option<FilterValue>) -> bool {
+        let Some(field) = field else {
+            return false;
+        };
+
+        match self.op {
+            FilterOp::Contains => match (&field, &self.value) {
+                (FilterValue::String(a), FilterValue::String(b)) => a.contains(b.as_str()),
+                _ => false,
+            },
+            FilterOp::Ne => matches!(
+                field.compare(&self.value),
+                Some(Ordering::Less | Ordering::Greater)
+            ),
+            FilterOp::Eq => field.compare(&self.value) == Some(Ordering::Equal),
+            FilterOp::Gt => field.compare(&self.value) == Some(Ordering::Greater),
+            FilterOp::Lt => field.compare(&self.value) == Some(Ordering::Less),
+        }
+    }
+}
+
+pub type DefaultFilterPage<K> = FilterPage<50, 500, K>;
+
+/// Page that only returns values matching the filter, scanning at most `MAX_SCAN` entries
+#[cw_serde]
+pub struct FilterPage<const LIMIT: usize, const MAX_SCAN: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    pub filter: Option<ValueFilter>,
+    pub max_scan: Option<usize>,
+}
+
+impl<const LIMIT: usize, const MAX_SCAN: usize, Key> FilterPage<LIMIT, MAX_SCAN, Key> {
+    pub fn into_filtered_pagination<'a, Value, Data, Project, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        project: Project,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Project: Fn(&str, &Value) -> Option<FilterValue>,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(max_scan);
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            if data.len() >= limit {
+                break;
+            }
+
+            let (key, value) = item?;
+            scanned += 1;
+            let matches = match &self.filter {
+                None => true,
+                Some(filter) => filter.matches(project(&filter.field, &value)),
+            };
+            if matches {
+                data.push(transform(&key, value));
+            }
+
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{FilterOp, FilterPage, FilterValue, Map, ValueFilter};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn value_filter() {
+        let filter = ValueFilter {
+            field: "name".to_string(),
+            op: FilterOp::Contains,
+            value: FilterValue::String("bob".to_string()),
+        };
+        assert!(filter.matches(Some(FilterValue::String("bobby".to_string()))));
+        assert!(!filter.matches(Some(FilterValue::String("alice".to_string()))));
+        assert!(!filter.matches(Some(FilterValue::Int(1))));
+        assert!(!filter.matches(None));
+
+        let filter = ValueFilter {
+            field: "amount".to_string(),
+            op: FilterOp::Gt,
+            value: FilterValue::Int(10),
+        };
+        assert!(filter.matches(Some(FilterValue::Int(11))));
+        assert!(!filter.matches(Some(FilterValue::Int(10))));
+        assert!(!filter.matches(Some(FilterValue::Uint(11u128.into()))));
+
+        let filter = ValueFilter {
+            field: "amount".to_string(),
+            op: FilterOp::Ne,
+            value: FilterValue::Int(10),
+        };
+        assert!(filter.matches(Some(FilterValue::Int(11))));
+        assert!(!filter.matches(Some(FilterValue::Int(10))));
+        assert!(!filter.matches(Some(FilterValue::String("10".to_string()))));
+        assert!(!filter.matches(None));
+    }
+
+    #[test]
+    fn into_filtered_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, i64> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map
+                .save(deps.as_mut().storage, i, &(i as i64 % 10))
+                .unwrap();
+        }
+
+        let project = |field: &str, value: &i64| match field {
+            "amount" => Some(FilterValue::Int(*value)),
+            _ => None,
+        };
+
+        let query: FilterPage<5, 100, _> = FilterPage {
+            start: None,
+            qty: None,
+            filter: Some(ValueFilter {
+                field: "amount".to_string(),
+                op: FilterOp::Eq,
+                value: FilterValue::Int(3),
+            }),
+            max_scan: None,
+        };
+
+        let res = query
+            .into_filtered_pagination(deps.as_ref().storage, &test_map, project, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, vec![3, 13, 23, 33, 43]);
+        assert_eq!(res.next, Some(43));
+        assert_eq!(res.qty, 5);
+        assert_eq!(res.scanned, 44);
+
+        let query: FilterPage<5, 100, _> = FilterPage {
+            start: res.next,
+            qty: None,
+            filter: Some(ValueFilter {
+                field: "amount".to_string(),
+                op: FilterOp::Eq,
+                value: FilterValue::Int(3),
+            }),
+            max_scan: Some(15),
+        };
+
+        let res = query
+            .into_filtered_pagination(deps.as_ref().storage, &test_map, project, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, vec![53]);
+        assert_eq!(res.next, Some(58));
+        assert_eq!(res.scanned, 15);
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{
+    decode_cursor, decode_directed_cursor, encode_cursor, encode_directed_cursor, StringCursor,
+};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Binary, Order};
+use std::fmt::Debug;
+
+/// Golden test for an opaque [`encode_cursor`] cursor. `fixture` is the base64 token as
+/// clients received it, record it once and keep it so an upgrade of the key type or of
+/// this crate that would break live cursors fails the test instead
+#[track_caller]
+pub fn assert_cursor_fixture<K>(version: u8, cursor: &K, fixture: &str)
+where
+    K: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let token = Binary::from_base64(fixture).unwrap();
+    assert_eq!(
+        decode_cursor::<K>(version, &token).unwrap(),
+        *cursor,
+        "decoded fixture"
+    );
+    assert_eq!(
+        encode_cursor(version, cursor).unwrap().to_base64(),
+        fixture,
+        "encoded cursor"
+    );
+}
+
+/// [`assert_cursor_fixture`] for cursors from [`encode_directed_cursor`]
+#[track_caller]
+pub fn assert_directed_cursor_fixture<K>(version: u8, order: Order, cursor: &K, fixture: &str)
+where
+    K: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let token = Binary::from_base64(fixture).unwrap();
+    assert_eq!(
+        decode_directed_cursor::<K>(version, order, &token).unwrap(),
+        *cursor,
+        "decoded fixture"
+    );
+    assert_eq!(
+        encode_directed_cursor(version, order, cursor)
+            .unwrap()
+            .to_base64(),
+        fixture,
+        "encoded cursor"
+    );
+}
+
+/// [`assert_cursor_fixture`] for the plain string cursors of [`StringCursor`]
+#[track_caller]
+pub fn assert_string_cursor_fixture<K>(cursor: &K, fixture: &str)
+where
+    K: StringCursor + PartialEq + Debug,
+{
+    assert_eq!(
+        K::decode_cursor(fixture).unwrap(),
+        *cursor,
+        "decoded fixture"
+    );
+    assert_eq!(cursor.encode_cursor(), fixture, "encoded cursor");
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assert_cursor_fixture, assert_directed_cursor_fixture, assert_string_cursor_fixture,
+    };
+    use cosmwasm_std::{Addr, Order};
+
+    // Tokens issued by earlier releases, they must keep decoding to the same keys
+    #[test]
+    fn cursor_fixtures() {
+        assert_cursor_fixture(1, &42u64, "ATQy");
+        assert_cursor_fixture(
+            2,
+            &(Addr::unchecked("osmo1abc"), 7u64),
+            "Alsib3NtbzFhYmMiLDdd",
+        );
+        assert_directed_cursor_fixture(1, Order::Descending, &"alice".to_string(), "AQEiYWxpY2Ui");
+        assert_string_cursor_fixture(&(Addr::unchecked("osmo1abc"), 7u64), "osmo1abc/7");
+        assert_string_cursor_fixture(&"a/b".to_string(), "a/b");
+    }
+
+    #[test]
+    #[should_panic(expected = "encoded cursor")]
+    fn non_canonical_fixture() {
+        assert_string_cursor_fixture(&("ax".to_string(), 1u8), "a\\x/1");
+    }
+}
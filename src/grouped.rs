@@ -0,0 +1,255 @@
+use crate::error::validate_qty;
+use crate::{Map, NextPage, Page, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, Prefixer, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultGroupedPage<P, S> = GroupedPage<10, 20, P, S>;
+
+/// Up to `per_prefix_limit` entries for each of `prefixes` in a single query, e.g. the
+/// top orders of several markets. `after` holds the cursors of the groups being resumed,
+/// prefixes without one start from the beginning
+#[cw_serde]
+pub struct GroupedPage<const LIMIT: usize, const MAX_GROUPS: usize, P, S> {
+    pub prefixes: Vec<P>,
+    #[serde(default)]
+    pub after: Vec<(P, S)>,
+    pub per_prefix_limit: Option<usize>,
+}
+
+/// Page of a single prefix, `page.next` resumes it through [`GroupedPage::after`]
+#[cw_serde]
+pub struct Group<P, D, S> {
+    pub prefix: P,
+    pub page: NextPage<D, S>,
+}
+
+impl<const LIMIT: usize, const MAX_GROUPS: usize, P, S> GroupedPage<LIMIT, MAX_GROUPS, P, S> {
+    /// Rejects more than `MAX_GROUPS` prefixes and a per prefix limit of zero or above `LIMIT`
+    pub fn validate(&self) -> Result<(), QueryError> {
+        if self.prefixes.len() > MAX_GROUPS {
+            return Err(QueryError::LimitExceeded {
+                requested: self.prefixes.len(),
+                max: MAX_GROUPS,
+            });
+        }
+        validate_qty(self.per_prefix_limit, LIMIT)
+    }
+
+    /// Validates the page and paginates every prefix, the groups keep the request order
+    pub fn into_grouped_pagination<'a, Key, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> Result<Vec<Group<P, Data, S::Output>>, QueryError>
+    where
+        Key: PrimaryKey<'a, Prefix = P, Suffix = S>,
+        P: Prefixer<'a> + Clone + PartialEq,
+        S: PrimaryKey<'a> + KeyDeserialize,
+        S::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&S::Output, Value) -> Data + Copy,
+    {
+        self.validate()?;
+        let limit = self.per_prefix_limit.unwrap_or(LIMIT);
+        let mut after = self.after;
+        let mut groups = Vec::with_capacity(self.prefixes.len());
+
+        for prefix in self.prefixes {
+            let start = after
+                .iter()
+                .position(|(p, _)| *p == prefix)
+                .map(|i| after.swap_remove(i).1);
+            let range = map
+                .prefix(prefix.clone())
+                .range(
+                    storage,
+                    start.map(|s| Bound::Exclusive((s, PhantomData))),
+                    None,
+                    Order::Ascending,
+                )
+                .take(limit);
+            let mut data = vec![];
+            let mut end = None;
+
+            for item in range {
+                let (key, value) = item?;
+                data.push(transform(&key, value));
+                end = Some(key);
+            }
+
+            let len = data.len();
+            groups.push(Group {
+                prefix,
+                page: NextPage {
+                    data,
+                    next: end,
+                    qty: len,
+                    scanned: len,
+                    errors: vec![],
+                },
+            });
+        }
+
+        Ok(groups)
+    }
+}
+
+impl<const LIMIT: usize, P, S> Page<LIMIT, (P, S)> {
+    /// Walks every prefix of a `(Prefix, Suffix)` keyed map, returning up to `LIMIT`
+    /// entries grouped by prefix. `next` is the compound cursor of the last entry, so
+    /// exports resume in the middle of a large prefix instead of stalling on it
+    pub fn into_per_prefix_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, (P, S), Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Group<P::Output, Data, S::Output>, (P::Output, S::Output)>>
+    where
+        (P, S): PrimaryKey<'a> + KeyDeserialize<Output = (P::Output, S::Output)>,
+        P: KeyDeserialize,
+        S: KeyDeserialize,
+        P::Output: PartialEq + Clone + 'static,
+        S::Output: Clone + 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&S::Output, Value) -> Data + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut groups: Vec<Group<P::Output, Data, S::Output>> = vec![];
+        let mut end = None;
+        let mut len = 0;
+
+        for item in range {
+            let ((prefix, suffix), value) = item?;
+            let res = transform(&suffix, value);
+            match groups.last_mut() {
+                Some(group) if group.prefix == prefix => group.page.data.push(res),
+                _ => groups.push(Group {
+                    prefix: prefix.clone(),
+                    page: NextPage {
+                        data: vec![res],
+                        next: None,
+                        qty: 0,
+                        scanned: 0,
+                        errors: vec![],
+                    },
+                }),
+            }
+
+            if let Some(group) = groups.last_mut() {
+                group.page.next = Some(suffix.clone());
+                group.page.qty += 1;
+                group.page.scanned += 1;
+            }
+            len += 1;
+            end = Some((prefix, suffix));
+        }
+
+        Ok(NextPage {
+            data: groups,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{GroupedPage, Map, Page, QueryError};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_grouped_pagination() {
+        let mut deps = mock_dependencies();
+        let orders: Map<(String, u64), u64> = Map::new("orders");
+
+        for market in ["atom", "juno", "osmo"] {
+            for id in 0..10 {
+                orders
+                    .save(deps.as_mut().storage, (market.to_string(), id), &(id * 10))
+                    .unwrap();
+            }
+        }
+
+        let query: GroupedPage<5, 2, String, u64> = GroupedPage {
+            prefixes: vec!["osmo".to_string(), "atom".to_string()],
+            after: vec![("atom".to_string(), 6)],
+            per_prefix_limit: Some(3),
+        };
+        let res = query
+            .into_grouped_pagination(deps.as_ref().storage, &orders, |_, v| v)
+            .unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].prefix, "osmo");
+        assert_eq!(res[0].page.data, vec![0, 10, 20]);
+        assert_eq!(res[0].page.next, Some(2));
+        assert_eq!(res[1].prefix, "atom");
+        assert_eq!(res[1].page.data, vec![70, 80, 90]);
+
+        let query: GroupedPage<5, 2, String, u64> = GroupedPage {
+            prefixes: vec!["atom".to_string(), "juno".to_string(), "osmo".to_string()],
+            after: vec![],
+            per_prefix_limit: None,
+        };
+        assert_eq!(
+            query
+                .into_grouped_pagination(deps.as_ref().storage, &orders, |_, v| v)
+                .unwrap_err(),
+            QueryError::LimitExceeded {
+                requested: 3,
+                max: 2
+            }
+        );
+    }
+
+    #[test]
+    fn into_per_prefix_pagination() {
+        let mut deps = mock_dependencies();
+        let orders: Map<(String, u64), u64> = Map::new("orders");
+
+        for (market, count) in [("atom", 2), ("juno", 5), ("osmo", 1)] {
+            for id in 0..count {
+                orders
+                    .save(deps.as_mut().storage, (market.to_string(), id), &id)
+                    .unwrap();
+            }
+        }
+
+        let query: Page<4, (String, u64)> = Page::first();
+        let res = query
+            .into_per_prefix_pagination(deps.as_ref().storage, &orders, |_, v| v)
+            .unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].prefix, "atom");
+        assert_eq!(res[0].page.data, vec![0, 1]);
+        assert_eq!(res[1].prefix, "juno");
+        assert_eq!(res[1].page.data, vec![0, 1]);
+        assert_eq!(res[1].page.next, Some(1));
+        assert_eq!(res.next, Some(("juno".to_string(), 1)));
+
+        // Resumes inside `juno`
+        let query: Page<4, (String, u64)> = Page::after(res.next.unwrap());
+        let res = query
+            .into_per_prefix_pagination(deps.as_ref().storage, &orders, |_, v| v)
+            .unwrap();
+        assert_eq!(res[0].prefix, "juno");
+        assert_eq!(res[0].page.data, vec![2, 3, 4]);
+        assert_eq!(res[1].prefix, "osmo");
+        assert_eq!(res[1].page.data, vec![0]);
+        assert_eq!(res.qty, 4);
+        assert_eq!(res.next, Some(("osmo".to_string(), 0)));
+    }
+}
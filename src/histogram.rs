@@ -0,0 +1,183 @@
+use crate::{Map, Page};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+#[cw_serde]
+pub struct Bucket<B> {
+    pub id: B,
+    pub count: u64,
+    pub sum: Uint128,
+}
+
+/// Per bucket totals of the scanned range sorted by bucket id, pages are combined
+/// client side with [`Histogram::merge`]
+#[cw_serde]
+pub struct Histogram<B, K> {
+    pub buckets: Vec<Bucket<B>>,
+    pub next: Option<K>,
+    pub scanned: usize,
+}
+
+impl<B: Ord, K> Histogram<B, K> {
+    /// Adds the totals of the following page, taking over its cursor
+    pub fn merge(&mut self, other: Histogram<B, K>) -> StdResult<()> {
+        let mut buckets: BTreeMap<B, (u64, Uint128)> = self
+            .buckets
+            .drain(..)
+            .map(|bucket| (bucket.id, (bucket.count, bucket.sum)))
+            .collect();
+        for bucket in other.buckets {
+            add(&mut buckets, bucket.id, bucket.count, bucket.sum)?;
+        }
+
+        self.buckets = into_buckets(buckets);
+        self.next = other.next;
+        self.scanned += other.scanned;
+        Ok(())
+    }
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Buckets the next `qty` entries by the id returned from `bucket`, counting them and
+    /// summing the amount it returns alongside, e.g. balances per denom or orders per day.
+    /// Large ranges are aggregated incrementally by following `next`
+    pub fn into_histogram<'a, Value, B, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        bucket: Function,
+    ) -> StdResult<Histogram<B, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        B: Ord,
+        Function: Fn(&Key::Output, &Value) -> (B, Uint128),
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut buckets = BTreeMap::new();
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            let (key, value) = item?;
+            let (id, amount) = bucket(&key, &value);
+            add(&mut buckets, id, 1, amount)?;
+            scanned += 1;
+            end = Some(key);
+        }
+
+        Ok(Histogram {
+            buckets: into_buckets(buckets),
+            next: end,
+            scanned,
+        })
+    }
+}
+
+fn add<B: Ord>(
+    buckets: &mut BTreeMap<B, (u64, Uint128)>,
+    id: B,
+    count: u64,
+    sum: Uint128,
+) -> StdResult<()> {
+    let totals = buckets.entry(id).or_insert((0, Uint128::zero()));
+    totals.0 += count;
+    totals.1 = totals.1.checked_add(sum)?;
+    Ok(())
+}
+
+fn into_buckets<B>(buckets: BTreeMap<B, (u64, Uint128)>) -> Vec<Bucket<B>> {
+    buckets
+        .into_iter()
+        .map(|(id, (count, sum))| Bucket { id, count, sum })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Bucket, Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Uint128;
+
+    #[test]
+    fn into_histogram() {
+        let mut deps = mock_dependencies();
+        let balances: Map<u32, (String, u128)> = Map::new("balances");
+
+        for i in 0..10 {
+            let denom = if i % 3 == 0 { "uatom" } else { "uosmo" };
+            balances
+                .save(
+                    deps.as_mut().storage,
+                    i,
+                    &(denom.to_string(), i as u128 * 10),
+                )
+                .unwrap();
+        }
+
+        let bucket =
+            |_: &u32, (denom, amount): &(String, u128)| (denom.clone(), Uint128::new(*amount));
+        let query: Page<6, u32> = Page {
+            start: None,
+            qty: None,
+        };
+        let mut res = query
+            .into_histogram(deps.as_ref().storage, &balances, bucket)
+            .unwrap();
+        assert_eq!(
+            res.buckets,
+            vec![
+                Bucket {
+                    id: "uatom".to_string(),
+                    count: 2,
+                    sum: Uint128::new(30),
+                },
+                Bucket {
+                    id: "uosmo".to_string(),
+                    count: 4,
+                    sum: Uint128::new(120),
+                },
+            ]
+        );
+        assert_eq!(res.next, Some(5));
+
+        let query: Page<6, u32> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let next = query
+            .into_histogram(deps.as_ref().storage, &balances, bucket)
+            .unwrap();
+        res.merge(next).unwrap();
+        assert_eq!(
+            res.buckets,
+            vec![
+                Bucket {
+                    id: "uatom".to_string(),
+                    count: 4,
+                    sum: Uint128::new(180),
+                },
+                Bucket {
+                    id: "uosmo".to_string(),
+                    count: 6,
+                    sum: Uint128::new(270),
+                },
+            ]
+        );
+        assert_eq!(res.next, Some(9));
+        assert_eq!(res.scanned, 10);
+    }
+}
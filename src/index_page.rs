@@ -0,0 +1,151 @@
+use crate::{Deque, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{StdError, StdResult, Storage};
+
+/// Storage addressed by insertion index, which is the only way to page over
+/// secret-toolkit's `AppendStore` and `Keymap` since Secret Network storage can't be iterated.
+/// The storage and error types are left generic so implementations can be written against
+/// `secret-cosmwasm-std` without this crate depending on it. Foreign stores such as
+/// `AppendStore` have to be wrapped in a contract local newtype to implement it.
+pub trait IndexedStorage<S: ?Sized> {
+    type Item;
+    type Error;
+
+    fn len(&self, storage: &S) -> Result<u32, Self::Error>;
+
+    fn get_at(&self, storage: &S, index: u32) -> Result<Self::Item, Self::Error>;
+}
+
+impl<'a, T> IndexedStorage<dyn Storage + 'a> for Deque<'_, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    type Item = T;
+    type Error = StdError;
+
+    fn len(&self, storage: &(dyn Storage + 'a)) -> StdResult<u32> {
+        Deque::len(self, storage)
+    }
+
+    fn get_at(&self, storage: &(dyn Storage + 'a), index: u32) -> StdResult<T> {
+        self.get(storage, index)?
+            .ok_or_else(|| StdError::not_found(std::any::type_name::<T>()))
+    }
+}
+
+pub type DefaultIndexPage = IndexPage<50>;
+
+/// Page over an [`IndexedStorage`] where the cursor is the last returned index
+#[cw_serde]
+pub struct IndexPage<const LIMIT: usize> {
+    pub start: Option<u32>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize> IndexPage<LIMIT> {
+    pub fn into_index_pagination<S, Store, Data, Function>(
+        self,
+        storage: &S,
+        store: &Store,
+        transform: Function,
+    ) -> Result<NextPage<Data, u32>, Store::Error>
+    where
+        S: ?Sized,
+        Store: IndexedStorage<S>,
+        Function: FnOnce(u32, Store::Item) -> Data + Copy,
+    {
+        let first = self.start.map(|s| s.saturating_add(1)).unwrap_or(0);
+        let last = first
+            .saturating_add(self.qty.unwrap_or(LIMIT) as u32)
+            .min(store.len(storage)?);
+        let mut data = vec![];
+        let mut end = None;
+
+        for index in first..last {
+            data.push(transform(index, store.get_at(storage, index)?));
+            end = Some(index);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Deque, IndexPage, IndexedStorage};
+    use cosmwasm_std::testing::mock_dependencies;
+    use std::convert::Infallible;
+
+    struct MemoryStore;
+
+    impl IndexedStorage<Vec<String>> for MemoryStore {
+        type Item = String;
+        type Error = Infallible;
+
+        fn len(&self, storage: &Vec<String>) -> Result<u32, Infallible> {
+            Ok(storage.len() as u32)
+        }
+
+        fn get_at(&self, storage: &Vec<String>, index: u32) -> Result<String, Infallible> {
+            Ok(storage[index as usize].clone())
+        }
+    }
+
+    #[test]
+    fn into_index_pagination() {
+        let storage: Vec<String> = (0..30).map(|i| format!("item-{}", i)).collect();
+
+        let query: IndexPage<20> = IndexPage {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_index_pagination(&storage, &MemoryStore, |_, v| v)
+            .unwrap();
+
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(19));
+        assert_eq!(res.data.first().unwrap(), "item-0");
+
+        let query: IndexPage<20> = IndexPage {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_index_pagination(&storage, &MemoryStore, |i, _| i)
+            .unwrap();
+
+        assert_eq!(res.data, (20..30).collect::<Vec<u32>>());
+        assert_eq!(res.next, Some(29));
+    }
+
+    #[test]
+    fn into_index_pagination_deque() {
+        let mut deps = mock_dependencies();
+        let deque: Deque<u64> = Deque::new("deque");
+
+        for i in 0..10 {
+            deque.push_back(deps.as_mut().storage, &(i * 2)).unwrap();
+        }
+
+        let query: IndexPage<20> = IndexPage {
+            start: Some(6),
+            qty: None,
+        };
+        let res = query
+            .into_index_pagination(deps.as_ref().storage, &deque, |_, v| v)
+            .unwrap();
+
+        assert_eq!(res.data, vec![14, 16, 18]);
+        assert_eq!(res.next, Some(9));
+    }
+}
@@ -0,0 +1,393 @@
+use crate::{IndexedMap, KeysIter, MultiIndex, NextPage, Page};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Binary, CustomQuery, Deps, Order, StdError, StdResult};
+use cw_storage_plus::{Bound, IndexList, KeyDeserialize, Prefixer, PrimaryKey};
+use std::any::type_name;
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+/// [`crate::MapQueryExt`] counterpart for `IndexedMap`, adding pagination over the
+/// entries of a `MultiIndex` prefix
+pub trait IndexedMapQueryExt<'a, Key, Value>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+{
+    fn paginate<C, const LIMIT: usize, Data, Function>(
+        &self,
+        deps: Deps<'a, C>,
+        page: Page<LIMIT, Key>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        C: CustomQuery,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy;
+
+    fn page_keys<C, const LIMIT: usize>(
+        &self,
+        deps: Deps<'a, C>,
+        page: Page<LIMIT, Key>,
+    ) -> KeysIter<'a, Key::Output>
+    where
+        C: CustomQuery;
+
+    /// Paginates the entries stored under `prefix` in the index, the cursor is the primary key
+    fn page_index<C, const LIMIT: usize, IndexKey, Data, Function>(
+        &self,
+        index: &MultiIndex<'a, IndexKey, Value, Key>,
+        deps: Deps<'a, C>,
+        prefix: IndexKey,
+        page: Page<LIMIT, Key>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        C: CustomQuery,
+        IndexKey: PrimaryKey<'a> + Prefixer<'a>,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy;
+
+    /// Paginates the entries under the leading elements `prefix` of a tuple index key,
+    /// leaving out primary keys already in the page, e.g. stale index entries left behind
+    /// by writes that bypassed the `IndexedMap`. Skipped entries use up the requested page
+    /// size and are counted in `scanned` but not in the returned `qty`, the cursor is the raw
+    /// index key of the last scanned entry
+    fn page_sub_index_unique<C, const LIMIT: usize, IndexKey, Data, Function>(
+        &self,
+        index: &MultiIndex<'a, IndexKey, Value, Key>,
+        deps: Deps<'a, C>,
+        prefix: IndexKey::Prefix,
+        page: Page<LIMIT, Binary>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Binary>>
+    where
+        C: CustomQuery,
+        IndexKey: PrimaryKey<'a> + Prefixer<'a>,
+        (IndexKey::Suffix, Key): PrimaryKey<'a>,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy;
+}
+
+impl<'a, Key, Value, Indexes> IndexedMapQueryExt<'a, Key, Value>
+    for IndexedMap<'a, Key, Value, Indexes>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    <Key as KeyDeserialize>::Output: 'static,
+    Value: Serialize + DeserializeOwned + Clone + 'a,
+    Indexes: IndexList<Value>,
+{
+    fn paginate<C, const LIMIT: usize, Data, Function>(
+        &self,
+        deps: Deps<'a, C>,
+        page: Page<LIMIT, Key>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        C: CustomQuery,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = self.range(
+            deps.storage,
+            page.start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+        collect_page(range, page.qty.unwrap_or(LIMIT), transform)
+    }
+
+    fn page_keys<C, const LIMIT: usize>(
+        &self,
+        deps: Deps<'a, C>,
+        page: Page<LIMIT, Key>,
+    ) -> KeysIter<'a, Key::Output>
+    where
+        C: CustomQuery,
+    {
+        let keys = self.keys(
+            deps.storage,
+            page.start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+        KeysIter::new(keys, page.qty.unwrap_or(LIMIT))
+    }
+
+    fn page_index<C, const LIMIT: usize, IndexKey, Data, Function>(
+        &self,
+        index: &MultiIndex<'a, IndexKey, Value, Key>,
+        deps: Deps<'a, C>,
+        prefix: IndexKey,
+        page: Page<LIMIT, Key>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        C: CustomQuery,
+        IndexKey: PrimaryKey<'a> + Prefixer<'a>,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = index.prefix(prefix).range(
+            deps.storage,
+            page.start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+        collect_page(range, page.qty.unwrap_or(LIMIT), transform)
+    }
+
+    fn page_sub_index_unique<C, const LIMIT: usize, IndexKey, Data, Function>(
+        &self,
+        index: &MultiIndex<'a, IndexKey, Value, Key>,
+        deps: Deps<'a, C>,
+        prefix: IndexKey::Prefix,
+        page: Page<LIMIT, Binary>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Binary>>
+    where
+        C: CustomQuery,
+        IndexKey: PrimaryKey<'a> + Prefixer<'a>,
+        (IndexKey::Suffix, Key): PrimaryKey<'a>,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let entries = index.sub_prefix(prefix);
+        // Index entries only resolve to their primary key, so each raw index key read is
+        // looked up on its own to keep the cursor on the index rather than the primary key
+        let raw_keys = entries
+            .keys_raw(
+                deps.storage,
+                page.start.map(|s| Bound::ExclusiveRaw(s.to_vec())),
+                None,
+                Order::Ascending,
+            )
+            .take(page.qty.unwrap_or(LIMIT));
+        let mut seen = HashSet::new();
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for raw_key in raw_keys {
+            scanned += 1;
+            let (pk, value) = entries
+                .range_raw(
+                    deps.storage,
+                    Some(Bound::InclusiveRaw(raw_key.clone())),
+                    Some(Bound::InclusiveRaw(raw_key.clone())),
+                    Order::Ascending,
+                )
+                .next()
+                .ok_or_else(|| StdError::not_found(type_name::<Value>()))??;
+            end = Some(Binary::from(raw_key));
+            if !seen.insert(pk.clone()) {
+                continue;
+            }
+
+            let key = Key::from_slice(&pk)?;
+            data.push(transform(&key, value));
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+fn collect_page<Key, Value, Data, Function>(
+    range: impl Iterator<Item = StdResult<(Key, Value)>>,
+    limit: usize,
+    transform: Function,
+) -> StdResult<NextPage<Data, Key>>
+where
+    Function: FnOnce(&Key, Value) -> Data + Copy,
+{
+    let mut data = vec![];
+    let mut end = None;
+
+    for item in range.take(limit) {
+        let (key, value) = item?;
+        data.push(transform(&key, value));
+        end = Some(key);
+    }
+
+    let len = data.len();
+    Ok(NextPage {
+        data,
+        next: end,
+        qty: len,
+        scanned: len,
+        errors: vec![],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{IndexedMap, IndexedMapQueryExt, Map, MultiIndex, Page};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Binary;
+    use cw_storage_plus::{Index, IndexList};
+
+    #[cw_serde]
+    struct Token {
+        owner: String,
+    }
+
+    struct TokenIndexes<'a> {
+        owner: MultiIndex<'a, String, Token, u32>,
+    }
+
+    impl<'a> IndexList<Token> for TokenIndexes<'a> {
+        fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Token>> + '_> {
+            let v: Vec<&dyn Index<Token>> = vec![&self.owner];
+            Box::new(v.into_iter())
+        }
+    }
+
+    fn tokens<'a>() -> IndexedMap<'static, u32, Token, TokenIndexes<'a>> {
+        IndexedMap::new(
+            "tokens",
+            TokenIndexes {
+                owner: MultiIndex::new(|_, t| t.owner.clone(), "tokens", "tokens__owner"),
+            },
+        )
+    }
+
+    #[test]
+    fn indexed_pagination() {
+        let mut deps = mock_dependencies();
+        let map = tokens();
+
+        for i in 0..100 {
+            map.save(
+                deps.as_mut().storage,
+                i,
+                &Token {
+                    owner: format!("owner-{}", i % 3),
+                },
+            )
+            .unwrap();
+        }
+
+        let page: Page<20, _> = Page {
+            start: Some(9),
+            qty: Some(3),
+        };
+        let res = map.paginate(deps.as_ref(), page, |k, _| *k).unwrap();
+        assert_eq!(res.data, vec![10, 11, 12]);
+        assert_eq!(res.next, Some(12));
+
+        let page: Page<20, _> = Page {
+            start: None,
+            qty: None,
+        };
+        let keys: Vec<u32> = map
+            .page_keys(deps.as_ref(), page)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(keys, (0..20).collect::<Vec<u32>>());
+
+        let page: Page<20, _> = Page {
+            start: Some(4),
+            qty: Some(4),
+        };
+        let res = map
+            .page_index(
+                &map.idx.owner,
+                deps.as_ref(),
+                "owner-1".to_string(),
+                page,
+                |k, v| (*k, v.owner),
+            )
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec![
+                (7, "owner-1".to_string()),
+                (10, "owner-1".to_string()),
+                (13, "owner-1".to_string()),
+                (16, "owner-1".to_string())
+            ]
+        );
+        assert_eq!(res.next, Some(16));
+    }
+
+    #[cw_serde]
+    struct Listing {
+        seller: String,
+        updated: u64,
+    }
+
+    struct ListingIndexes<'a> {
+        seller: MultiIndex<'a, (String, u64), Listing, u32>,
+    }
+
+    impl<'a> IndexList<Listing> for ListingIndexes<'a> {
+        fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Listing>> + '_> {
+            let v: Vec<&dyn Index<Listing>> = vec![&self.seller];
+            Box::new(v.into_iter())
+        }
+    }
+
+    #[test]
+    fn page_sub_index_unique() {
+        let mut deps = mock_dependencies();
+        let map = IndexedMap::new(
+            "listings",
+            ListingIndexes {
+                seller: MultiIndex::new(
+                    |_, l: &Listing| (l.seller.clone(), l.updated),
+                    "listings",
+                    "listings__seller",
+                ),
+            },
+        );
+        let listing = |updated| Listing {
+            seller: "alice".to_string(),
+            updated,
+        };
+
+        for id in 0..4 {
+            map.save(deps.as_mut().storage, id, &listing(id as u64))
+                .unwrap();
+        }
+        // A migration rewrote listing 1 without going through the indexes, the next save
+        // can't find its original index entry anymore
+        let primary: Map<u32, Listing> = Map::new("listings");
+        primary
+            .save(deps.as_mut().storage, 1, &listing(20))
+            .unwrap();
+        map.save(deps.as_mut().storage, 1, &listing(10)).unwrap();
+
+        let page: Page<3, Binary> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = map
+            .page_sub_index_unique(
+                &map.idx.seller,
+                deps.as_ref(),
+                "alice".to_string(),
+                page,
+                |k, v| (*k, v.updated),
+            )
+            .unwrap();
+        // The stale entry of listing 1 comes first and loads the current value
+        assert_eq!(res.data, vec![(0, 0), (1, 10), (2, 2)]);
+
+        let page: Page<3, Binary> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = map
+            .page_sub_index_unique(
+                &map.idx.seller,
+                deps.as_ref(),
+                "alice".to_string(),
+                page,
+                |k, v| (*k, v.updated),
+            )
+            .unwrap();
+        // Duplicates are only removed within a page
+        assert_eq!(res.data, vec![(3, 3), (1, 10)]);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::error::deserialize_key;
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::any::type_name;
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+pub type DefaultIntersectPage<K> = IntersectPage<50, 500, K>;
+
+/// Pages over the keys present in both maps, a set can be represented as a map with
+/// `Empty` values. Walks the keys of both maps, reading at most `MAX_SCAN` of them, and
+/// only loads the values of shared keys
+#[cw_serde]
+pub struct IntersectPage<const LIMIT: usize, const MAX_SCAN: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    pub max_scan: Option<usize>,
+}
+
+impl<const LIMIT: usize, const MAX_SCAN: usize, Key> IntersectPage<LIMIT, MAX_SCAN, Key> {
+    pub fn into_intersect_pagination<'a, First, Second, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        first: &Map<'a, Key, First>,
+        second: &Map<'a, Key, Second>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        First: Serialize + DeserializeOwned + 'a,
+        Second: Serialize + DeserializeOwned + 'a,
+        Function: FnOnce(&Key::Output, First, Second) -> Data + Copy,
+    {
+        let mut first_keys = first.keys_raw(
+            storage,
+            self.start
+                .clone()
+                .map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+        let mut second_keys = second.keys_raw(
+            storage,
+            self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+
+        let limit = self.qty.unwrap_or(LIMIT);
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let mut data = vec![];
+        let mut end = None;
+        let mut last = None;
+        let mut scanned = 0;
+
+        let mut left = first_keys.next();
+        let mut right = second_keys.next();
+        scanned += usize::from(left.is_some()) + usize::from(right.is_some());
+
+        while data.len() < limit && scanned < max_scan {
+            let (Some(left_key), Some(right_key)) = (&left, &right) else {
+                break;
+            };
+
+            match left_key.cmp(right_key) {
+                Ordering::Less => {
+                    last = left.take();
+                    left = first_keys.next();
+                    scanned += usize::from(left.is_some());
+                }
+                Ordering::Greater => {
+                    last = right.take();
+                    right = second_keys.next();
+                    scanned += usize::from(right.is_some());
+                }
+                Ordering::Equal => {
+                    let raw_key = left.take().unwrap();
+                    let first_value = load_raw(storage, first, &raw_key)?;
+                    let second_value = load_raw(storage, second, &raw_key)?;
+                    let key = deserialize_key::<Key>(raw_key.clone())?;
+                    data.push(transform(&key, first_value, second_value));
+                    end = Some(key);
+                    last = Some(raw_key);
+
+                    left = first_keys.next();
+                    right = second_keys.next();
+                    scanned += usize::from(left.is_some()) + usize::from(right.is_some());
+                }
+            }
+        }
+
+        // Stopped by the scan cap, resume after the last key read instead of the last match
+        if data.len() < limit && left.is_some() && right.is_some() {
+            if let Some(raw_key) = last {
+                end = Some(deserialize_key::<Key>(raw_key)?);
+            }
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+/// Value stored under the raw key, read with a range over that single key
+fn load_raw<'a, K, V>(storage: &'a dyn Storage, map: &Map<'a, K, V>, raw_key: &[u8]) -> StdResult<V>
+where
+    K: PrimaryKey<'a>,
+    V: Serialize + DeserializeOwned + 'a,
+{
+    let bound = || Some(Bound::InclusiveRaw(raw_key.to_vec()));
+    let (_, value) = map
+        .range_raw(storage, bound(), bound(), Order::Ascending)
+        .next()
+        .ok_or_else(|| StdError::not_found(type_name::<V>()))??;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{IntersectPage, Map};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Empty;
+
+    #[test]
+    fn into_intersect_pagination() {
+        let mut deps = mock_dependencies();
+        let members: Map<u32, Empty> = Map::new("members");
+        let stakers: Map<u32, u128> = Map::new("stakers");
+
+        for i in 0..100 {
+            if i % 2 == 0 {
+                members.save(deps.as_mut().storage, i, &Empty {}).unwrap();
+            }
+            if i % 3 == 0 {
+                stakers
+                    .save(deps.as_mut().storage, i, &(i as u128 * 10))
+                    .unwrap();
+            }
+        }
+
+        let query: IntersectPage<5, 100, _> = IntersectPage {
+            start: None,
+            qty: None,
+            max_scan: None,
+        };
+
+        let res = query
+            .into_intersect_pagination(deps.as_ref().storage, &members, &stakers, |k, _, v| (*k, v))
+            .unwrap();
+
+        assert_eq!(res.qty, 5);
+        assert_eq!(
+            res.data,
+            vec![(0, 0), (6, 60), (12, 120), (18, 180), (24, 240)]
+        );
+        assert_eq!(res.next, Some(24));
+
+        let query: IntersectPage<5, 100, _> = IntersectPage {
+            start: Some(90),
+            qty: None,
+            max_scan: None,
+        };
+
+        let res = query
+            .into_intersect_pagination(deps.as_ref().storage, &members, &stakers, |k, _, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, vec![96]);
+        assert_eq!(res.next, Some(96));
+
+        // Disjoint key ranges stop at the scan cap with the cursor moved forward
+        let query: IntersectPage<5, 100, _> = IntersectPage {
+            start: Some(0),
+            qty: None,
+            max_scan: Some(4),
+        };
+        let res = query
+            .into_intersect_pagination(deps.as_ref().storage, &members, &stakers, |k, _, _| *k)
+            .unwrap();
+        assert!(res.data.is_empty());
+        assert_eq!(res.scanned, 4);
+        assert_eq!(res.next, Some(3));
+    }
+}
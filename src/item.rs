@@ -0,0 +1,96 @@
+use crate::{Item, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{StdResult, Storage};
+
+pub type DefaultItemVecPage = ItemVecPage<50>;
+
+/// Page over a `Vec` stored in a single `Item`, the cursor is the last returned index
+#[cw_serde]
+pub struct ItemVecPage<const LIMIT: usize> {
+    pub start: Option<u32>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize> ItemVecPage<LIMIT> {
+    pub fn into_item_vec_pagination<T, Data, Function>(
+        self,
+        storage: &dyn Storage,
+        item: &Item<'static, Vec<T>>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, u32>>
+    where
+        T: Serialize + DeserializeOwned,
+        Function: FnOnce(u32, T) -> Data + Copy,
+    {
+        let first = self.start.map(|s| s.saturating_add(1)).unwrap_or(0);
+        let items = item.may_load(storage)?.unwrap_or_default();
+        let mut data = vec![];
+        let mut end = None;
+
+        for (index, value) in (first..)
+            .zip(items.into_iter().skip(first as usize))
+            .take(self.qty.unwrap_or(LIMIT))
+        {
+            data.push(transform(index, value));
+            end = Some(index);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Item, ItemVecPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_item_vec_pagination() {
+        let mut deps = mock_dependencies();
+        let test_item: Item<Vec<String>> = Item::new("test_item");
+
+        let query: ItemVecPage<20> = ItemVecPage {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_item_vec_pagination(deps.as_ref().storage, &test_item, |_, v| v)
+            .unwrap();
+        assert_eq!(res.qty, 0);
+        assert_eq!(res.next, None);
+
+        let items: Vec<String> = (0..30).map(|i| format!("item-{}", i)).collect();
+        test_item.save(deps.as_mut().storage, &items).unwrap();
+
+        let query: ItemVecPage<20> = ItemVecPage {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_item_vec_pagination(deps.as_ref().storage, &test_item, |_, v| v)
+            .unwrap();
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.data.first().unwrap(), "item-0");
+        assert_eq!(res.next, Some(19));
+
+        let query: ItemVecPage<20> = ItemVecPage {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_item_vec_pagination(deps.as_ref().storage, &test_item, |i, v| (i, v))
+            .unwrap();
+        assert_eq!(res.qty, 10);
+        assert_eq!(res.data.first().unwrap(), &(20, "item-20".to_string()));
+        assert_eq!(res.next, Some(29));
+    }
+}
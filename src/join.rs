@@ -0,0 +1,115 @@
+use crate::{Map, NextPage, Page};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Same as `into_pagination` but loads the `secondary` record keyed by `join` for each
+    /// entry, e.g. the market metadata of each order. Missing records are passed as `None`
+    pub fn into_joined_pagination<'a, 'b, Value, JoinKey, Joined, Data, Join, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        secondary: &Map<'b, JoinKey, Joined>,
+        join: Join,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        JoinKey: PrimaryKey<'b>,
+        Joined: Serialize + DeserializeOwned,
+        Join: Fn(&Value) -> JoinKey,
+        Function: FnOnce(&Key::Output, Value, Option<Joined>) -> Data + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for item in range {
+            let (key, value) = item?;
+            let joined = secondary.may_load(storage, join(&value))?;
+            data.push(transform(&key, value, joined));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_joined_pagination() {
+        let mut deps = mock_dependencies();
+        let orders: Map<u64, (String, u32)> = Map::new("orders");
+        let markets: Map<String, String> = Map::new("markets");
+
+        markets
+            .save(
+                deps.as_mut().storage,
+                "atom".to_string(),
+                &"ATOM/USDC".to_string(),
+            )
+            .unwrap();
+        markets
+            .save(
+                deps.as_mut().storage,
+                "osmo".to_string(),
+                &"OSMO/USDC".to_string(),
+            )
+            .unwrap();
+        for (id, market) in ["atom", "osmo", "juno", "atom"].iter().enumerate() {
+            orders
+                .save(
+                    deps.as_mut().storage,
+                    id as u64,
+                    &(market.to_string(), id as u32 * 10),
+                )
+                .unwrap();
+        }
+
+        let query: Page<3, u64> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_joined_pagination(
+                deps.as_ref().storage,
+                &orders,
+                &markets,
+                |(market, _)| market.clone(),
+                |id, (_, amount), market| (*id, amount, market),
+            )
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec![
+                (0, 0, Some("ATOM/USDC".to_string())),
+                (1, 10, Some("OSMO/USDC".to_string())),
+                (2, 20, None),
+            ]
+        );
+        assert_eq!(res.next, Some(2));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{Map, NextPage, Page};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Same as `into_pagination` but returns each item next to its key, so transforms don't
+    /// have to copy the key into their output. Pairs serialize as json arrays, unlike a map
+    /// whose keys would have to be strings
+    pub fn into_map_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<(Key::Output, Data), Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: Clone + 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+
+        for item in range {
+            let (key, value) = item?;
+            let res = transform(&key, value);
+            data.push((key, res));
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            next: data.last().map(|(key, _)| key.clone()),
+            data,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<K: Ord, D> NextPage<(K, D), K> {
+    /// Items of the page by key, for lookups inside the contract
+    pub fn into_btree_map(self) -> BTreeMap<K, D> {
+        self.data.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn into_map_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+
+        for i in 0..10 {
+            test_map.save(deps.as_mut().storage, i, &(i * 10)).unwrap();
+        }
+
+        let query: Page<3, u32> = Page {
+            start: Some(4),
+            qty: None,
+        };
+        let res = query
+            .into_map_pagination(deps.as_ref().storage, &test_map, |_, v| v + 1)
+            .unwrap();
+        assert_eq!(res.data, vec![(5, 51), (6, 61), (7, 71)]);
+        assert_eq!(res.next, Some(7));
+        assert_eq!(
+            res.into_btree_map(),
+            BTreeMap::from([(5, 51), (6, 61), (7, 71)])
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{KeysIter, KeysQuery, Map, NextPage, PaginatedQuery};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultLatestPage<K> = LatestPage<50, K>;
+
+/// Most recent first listing, iterates in descending key order starting right `before`
+/// the given key. The returned cursor is the oldest key of the page
+#[cw_serde]
+pub struct LatestPage<const LIMIT: usize, K> {
+    pub before: Option<K>,
+    pub qty: Option<usize>,
+}
+
+impl<'a, const LIMIT: usize, Key, Value, Data> PaginatedQuery<'a, Key, Value, Data>
+    for LatestPage<LIMIT, Key>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    <Key as KeyDeserialize>::Output: 'static,
+    Value: Serialize + DeserializeOwned,
+{
+    type POutput = NextPage<Data, Key::Output>;
+    type FuncKey = Key::Output;
+
+    fn into_pagination<Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<Self::POutput>
+    where
+        Function: FnOnce(&Self::FuncKey, Value) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let range = map
+            .range(
+                storage,
+                None,
+                self.before.map(|b| Bound::Exclusive((b, PhantomData))),
+                Order::Descending,
+            )
+            .take(limit);
+        let mut data = Vec::with_capacity(limit.min(LIMIT));
+        let mut end = None;
+
+        for item in range {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Value> KeysQuery<'a, Key, Value> for LatestPage<LIMIT, Key>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    <Key as KeyDeserialize>::Output: 'static,
+    Value: Serialize + DeserializeOwned + 'a,
+{
+    type KOutput = Key::Output;
+    fn keys(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> KeysIter<'a, Self::KOutput> {
+        let keys = map.keys(
+            storage,
+            None,
+            self.before.map(|b| Bound::Exclusive((b, PhantomData))),
+            Order::Descending,
+        );
+        KeysIter::new(keys, self.qty.unwrap_or(LIMIT))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{KeysQuery, LatestPage, Map, PaginatedQuery};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::StdResult;
+
+    #[test]
+    fn into_pagination() {
+        let mut deps = mock_dependencies();
+        let events: Map<u64, String> = Map::new("events");
+
+        for height in 0..10 {
+            events
+                .save(deps.as_mut().storage, height, &format!("event-{}", height))
+                .unwrap();
+        }
+
+        let query: LatestPage<4, u64> = LatestPage {
+            before: None,
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &events, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![9, 8, 7, 6]);
+        assert_eq!(res.next, Some(6));
+
+        let query: LatestPage<4, u64> = LatestPage {
+            before: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &events, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["event-5", "event-4", "event-3", "event-2"]);
+
+        let query: LatestPage<4, u64> = LatestPage {
+            before: Some(2),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &events, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![1, 0]);
+
+        let query: LatestPage<4, u64> = LatestPage {
+            before: Some(7),
+            qty: Some(2),
+        };
+        let keys: Vec<u64> = query
+            .keys(deps.as_ref().storage, &events)
+            .collect::<StdResult<_>>()
+            .unwrap();
+        assert_eq!(keys, vec![6, 5]);
+    }
+}
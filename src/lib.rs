@@ -1,21 +1,293 @@
+#[cfg(not(any(feature = "cosmwasm_1", feature = "cosmwasm_2")))]
+compile_error!("Either the `cosmwasm_1` or `cosmwasm_2` feature must be enabled");
+
+#[cfg(all(feature = "cosmwasm_1", feature = "cosmwasm_2"))]
+compile_error!("The `cosmwasm_1` and `cosmwasm_2` features can't be enabled together, disable default features to use `cosmwasm_2`");
+
+#[cfg(feature = "cosmwasm_2")]
+extern crate cosmwasm_schema_v2 as cosmwasm_schema;
+#[cfg(feature = "cosmwasm_2")]
+extern crate cosmwasm_std_v2 as cosmwasm_std;
+#[cfg(feature = "cosmwasm_2")]
+extern crate cw_storage_plus_v2 as cw_storage_plus;
+
+#[cfg(not(feature = "cosmwasm_2"))]
+extern crate cosmwasm_schema_v1 as cosmwasm_schema;
+#[cfg(not(feature = "cosmwasm_2"))]
+extern crate cosmwasm_std_v1 as cosmwasm_std;
+#[cfg(not(feature = "cosmwasm_2"))]
+extern crate cw_storage_plus_v1 as cw_storage_plus;
+
+pub mod aggregate;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+pub mod batch;
+pub mod builder;
+#[cfg(feature = "checksum")]
+pub mod checksum;
+pub mod chunk;
+pub mod client;
+pub mod collect;
+pub mod compat;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod config;
+pub mod cursor;
+pub mod dynamic;
+#[cfg(any(feature = "borsh", feature = "msgpack"))]
+pub mod encoding;
+pub mod enumerate;
+#[cfg(feature = "envelope")]
+pub mod envelope;
+pub mod error;
+pub mod expiry;
+pub mod export;
+pub mod ext;
+pub mod extremes;
+pub mod filter;
+#[cfg(feature = "testing")]
+pub mod fixtures;
+pub mod grouped;
+pub mod histogram;
+pub mod index_page;
+pub mod indexed;
+pub mod intersect;
+pub mod item;
+pub mod join;
+pub mod keyed;
+pub mod latest;
+pub mod lookup;
+pub mod macros;
+pub mod multi;
+pub mod mutation;
+pub mod namespaced;
+pub mod nested_vec;
+pub mod paginatable;
+pub mod pending;
 pub mod prefix;
+pub mod prefix_keys;
+pub mod prefix_range;
+#[cfg(feature = "projection")]
+pub mod projection;
 pub mod query;
+pub mod range;
+#[cfg(feature = "recipes")]
+pub mod recipes;
+pub mod registry;
+pub mod sample;
+pub mod seek;
+pub mod sharded;
+#[cfg(feature = "signed-cursors")]
+pub mod signed;
+pub mod snapshot;
+pub mod stop;
+pub mod string_cursor;
+pub mod sub_prefix;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod token;
+pub mod tuple_cursor;
+pub mod union;
+pub mod variant;
+pub mod versioned;
 
+pub use aggregate::*;
+#[cfg(feature = "proptest")]
+pub use arbitrary::*;
+pub use batch::*;
+pub use builder::*;
+#[cfg(feature = "checksum")]
+pub use checksum::*;
+pub use chunk::*;
+pub use client::*;
+pub use collect::*;
+pub use compat::*;
+#[cfg(feature = "compression")]
+pub use compression::*;
+pub use config::*;
+pub use cursor::*;
+pub use dynamic::*;
+#[cfg(any(feature = "borsh", feature = "msgpack"))]
+pub use encoding::*;
+pub use enumerate::*;
+#[cfg(feature = "envelope")]
+pub use envelope::*;
+pub use error::*;
+pub use expiry::*;
+pub use export::*;
+pub use ext::*;
+pub use extremes::*;
+pub use filter::*;
+#[cfg(feature = "testing")]
+pub use fixtures::*;
+pub use grouped::*;
+pub use histogram::*;
+pub use index_page::*;
+pub use indexed::*;
+pub use intersect::*;
+pub use item::*;
+pub use join::*;
+pub use keyed::*;
+pub use latest::*;
+pub use lookup::*;
+pub use macros::*;
+pub use multi::*;
+pub use mutation::*;
+pub use namespaced::*;
+pub use nested_vec::*;
+pub use paginatable::*;
+pub use pending::*;
 pub use prefix::*;
+pub use prefix_keys::*;
+pub use prefix_range::*;
+#[cfg(feature = "projection")]
+pub use projection::*;
 pub use query::*;
+pub use range::*;
+#[cfg(feature = "recipes")]
+pub use recipes::*;
+pub use registry::*;
+pub use sample::*;
+pub use seek::*;
+pub use sharded::*;
+#[cfg(feature = "signed-cursors")]
+pub use signed::*;
+pub use snapshot::*;
+pub use stop::*;
+pub use string_cursor::*;
+pub use sub_prefix::*;
+#[cfg(feature = "testing")]
+pub use testing::*;
+pub use token::*;
+pub use tuple_cursor::*;
+pub use union::*;
+pub use variant::*;
+pub use versioned::*;
+
+#[cfg(feature = "derive")]
+pub use cw_query_derive::paginated;
+
+/// Paths used by the crate's macro expansions, not part of the public API
+#[doc(hidden)]
+pub mod __private {
+    pub use cosmwasm_schema::cw_serde;
+    pub use cosmwasm_std::{Deps, StdResult};
+    pub use cw_storage_plus::KeyDeserialize;
+}
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{StdResult, Storage};
-use cw_storage_plus::Map;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{
+    to_json_binary, to_json_string, Attribute, Binary, Empty, Order, Record, StdResult, Storage,
+};
+use cw_storage_plus::{range_with_prefix, Key, Prefix, PrimaryKey, RawBound};
 use std::iter::Take;
+use std::ops::Deref;
+
+/// Smart query responses above this size are rejected by the chain
+pub const MAX_QUERY_RESPONSE_BYTES: usize = 128 * 1024;
 
 #[cw_serde]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct NextPage<D, K> {
     pub data: Vec<D>,
     pub next: Option<K>,
     pub qty: usize,
+    /// Entries read from storage to build the page, above `qty` when entries were filtered
+    /// out or skipped
+    #[serde(default)]
+    pub scanned: usize,
+    /// Entries skipped under [`ErrorPolicy::Skip`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "borsh", borsh(skip))]
+    pub errors: Vec<PageError>,
+}
+
+impl<D, K: Serialize> NextPage<D, K> {
+    /// Summarizes the page as `{prefix}_qty` and `{prefix}_next` attributes,
+    /// the cursor is json encoded
+    pub fn into_attributes(&self, prefix: &str) -> StdResult<Vec<Attribute>> {
+        Ok(vec![
+            Attribute::new(format!("{}_qty", prefix), self.qty.to_string()),
+            Attribute::new(format!("{}_next", prefix), to_json_string(&self.next)?),
+        ])
+    }
+}
+
+impl<D, K> NextPage<D, K> {
+    pub fn iter(&self) -> std::slice::Iter<'_, D> {
+        self.data.iter()
+    }
+
+    /// Amount of items in the page, may differ from `qty` for pages that count scanned entries
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<D, K> Deref for NextPage<D, K> {
+    type Target = [D];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
 }
 
+impl<D, K> IntoIterator for NextPage<D, K> {
+    type Item = D;
+    type IntoIter = std::vec::IntoIter<D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<'p, D, K> IntoIterator for &'p NextPage<D, K> {
+    type Item = &'p D;
+    type IntoIter = std::slice::Iter<'p, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+/// Type erased [`NextPage`] for proxy contracts and routers that forward pages without
+/// knowing the item type, `data` holds the json encoded items and `next` the json
+/// encoded cursor
+#[cw_serde]
+pub struct PageResponse {
+    pub data: Binary,
+    pub next: Option<Binary>,
+    pub qty: usize,
+    #[serde(default)]
+    pub scanned: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<PageError>,
+}
+
+impl<D: Serialize, K: Serialize> NextPage<D, K> {
+    pub fn into_binary_response(self) -> StdResult<PageResponse> {
+        Ok(PageResponse {
+            data: to_json_binary(&self.data)?,
+            next: self.next.as_ref().map(to_json_binary).transpose()?,
+            qty: self.qty,
+            scanned: self.scanned,
+            errors: self.errors,
+        })
+    }
+}
+
+/// Page of undecoded values keyed by their deserialized key
+pub type RawNextPage<K> = NextPage<(K, Binary), K>;
+
 pub trait PaginatedQuery<'a, Key, Value, Data> {
     /// Expected pagination output
     type POutput;
@@ -25,18 +297,217 @@ pub trait PaginatedQuery<'a, Key, Value, Data> {
     fn into_pagination<Function>(
         self,
         storage: &'a dyn Storage,
-        map: &Map<'static, Key, Value>,
+        map: &Map<'a, Key, Value>,
         transform: Function,
     ) -> StdResult<Self::POutput>
     where
         Function: FnOnce(&Self::FuncKey, Value) -> Data + Copy;
 }
 
+/// Keys returned by [`KeysQuery::keys`], at most the page's `qty` of them
+pub struct KeysIter<'a, K> {
+    inner: Take<Box<dyn Iterator<Item = StdResult<K>> + 'a>>,
+}
+
+impl<'a, K> KeysIter<'a, K> {
+    pub(crate) fn new(iter: Box<dyn Iterator<Item = StdResult<K>> + 'a>, limit: usize) -> Self {
+        KeysIter {
+            inner: iter.take(limit),
+        }
+    }
+
+    /// Collects the keys into a page whose cursor is the last key
+    pub fn collect_page(self) -> StdResult<NextPage<K, K>>
+    where
+        K: Clone,
+    {
+        let data = self.collect::<StdResult<Vec<K>>>()?;
+        let len = data.len();
+        Ok(NextPage {
+            next: data.last().cloned(),
+            data,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<'a, K> Iterator for KeysIter<'a, K> {
+    type Item = StdResult<K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
 pub trait KeysQuery<'a, Key, Value> {
     type KOutput;
     fn keys(
         self,
         storage: &'a dyn Storage,
-        map: &Map<'static, Key, Value>,
-    ) -> Take<Box<dyn Iterator<Item = StdResult<Self::KOutput>> + 'a>>;
+        map: &Map<'a, Key, Value>,
+    ) -> KeysIter<'a, Self::KOutput>;
+}
+
+pub trait PaginatedMutation<'a, Key, Value> {
+    /// Cursor returned after mutating the page
+    type MOutput;
+
+    /// Removes every entry in the page
+    fn delete(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> StdResult<MutatedPage<Self::MOutput>>;
+
+    /// Rewrites every value in the page, returning `None` removes the entry
+    fn update<Function>(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+        action: Function,
+    ) -> StdResult<MutatedPage<Self::MOutput>>
+    where
+        Function: FnMut(&Self::MOutput, Value) -> StdResult<Option<Value>>;
+
+    /// Copies the page into `new_map` under the keys returned by `transform`,
+    /// removing the old entries when `delete_old` is set
+    fn rekey<NewKey, NewValue, Function>(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+        new_map: &Map<'a, NewKey, NewValue>,
+        delete_old: bool,
+        transform: Function,
+    ) -> StdResult<MutatedPage<Self::MOutput>>
+    where
+        NewKey: PrimaryKey<'a>,
+        NewValue: Serialize + DeserializeOwned,
+        Function: FnMut(&Self::MOutput, Value) -> (NewKey, NewValue);
+}
+
+/// Iterates the undecoded key/value pairs stored under the namespace and sub namespaces,
+/// starting after the given joined key
+pub(crate) fn raw_range<'a>(
+    storage: &'a dyn Storage,
+    namespace: &[u8],
+    sub_names: &[Key],
+    start: Option<Vec<u8>>,
+    order: Order,
+) -> Box<dyn Iterator<Item = Record> + 'a> {
+    let prefix: Prefix<Vec<u8>, Empty> = Prefix::new(namespace, sub_names);
+    range_with_prefix(
+        storage,
+        &prefix,
+        start.map(RawBound::Exclusive),
+        None,
+        order,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::NextPage;
+    use cosmwasm_std::{from_json, Attribute};
+
+    #[test]
+    fn into_attributes() {
+        let page = NextPage {
+            data: vec![1, 2, 3],
+            next: Some("cursor".to_string()),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+
+        assert_eq!(
+            page.into_attributes("accounts").unwrap(),
+            vec![
+                Attribute::new("accounts_qty", "3"),
+                Attribute::new("accounts_next", "\"cursor\""),
+            ]
+        );
+
+        let page: NextPage<u8, u8> = NextPage {
+            data: vec![],
+            next: None,
+            qty: 0,
+            scanned: 0,
+            errors: vec![],
+        };
+
+        assert_eq!(
+            page.into_attributes("accounts").unwrap(),
+            vec![
+                Attribute::new("accounts_qty", "0"),
+                Attribute::new("accounts_next", "null"),
+            ]
+        );
+    }
+
+    #[test]
+    fn into_binary_response() {
+        let page = NextPage {
+            data: vec![1u8, 2, 3],
+            next: Some("cursor".to_string()),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+        let res = page.into_binary_response().unwrap();
+
+        assert_eq!(from_json::<Vec<u8>>(&res.data).unwrap(), vec![1, 2, 3]);
+        assert_eq!(
+            from_json::<String>(&res.next.unwrap()).unwrap(),
+            "cursor".to_string()
+        );
+        assert_eq!(res.qty, 3);
+
+        let page: NextPage<u8, u8> = NextPage {
+            data: vec![],
+            next: None,
+            qty: 0,
+            scanned: 0,
+            errors: vec![],
+        };
+        assert_eq!(page.into_binary_response().unwrap().next, None);
+    }
+
+    #[test]
+    fn collection_accessors() {
+        let page = NextPage {
+            data: vec![1u8, 2, 3],
+            next: Some(3u8),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+
+        assert_eq!(page.len(), 3);
+        assert!(!page.is_empty());
+        assert_eq!(page.iter().sum::<u8>(), 6);
+        assert_eq!(page[1], 2);
+        assert_eq!(page.last(), Some(&3));
+
+        let mut doubled = vec![];
+        for item in &page {
+            doubled.push(item * 2);
+        }
+        assert_eq!(doubled, vec![2, 4, 6]);
+        assert_eq!(page.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        let page: NextPage<u8, u8> = NextPage {
+            data: vec![],
+            next: None,
+            qty: 0,
+            scanned: 0,
+            errors: vec![],
+        };
+        assert!(page.is_empty());
+    }
 }
@@ -0,0 +1,191 @@
+use crate::Map;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{KeyDeserialize, Prefixer, PrimaryKey};
+
+/// Entry with the smallest key, or `None` when the map is empty
+pub fn first<'a, K, V>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, V>,
+) -> StdResult<Option<(K::Output, V)>>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    V: Serialize + DeserializeOwned,
+{
+    map.range(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()
+}
+
+/// Entry with the largest key, or `None` when the map is empty
+pub fn last<'a, K, V>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, V>,
+) -> StdResult<Option<(K::Output, V)>>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    V: Serialize + DeserializeOwned,
+{
+    map.range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+}
+
+/// Entry with the smallest suffix under `prefix`
+pub fn first_in_prefix<'a, K, V>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, V>,
+    prefix: K::Prefix,
+) -> StdResult<Option<(<K::Suffix as KeyDeserialize>::Output, V)>>
+where
+    K: PrimaryKey<'a>,
+    K::Prefix: Prefixer<'a>,
+    K::Suffix: PrimaryKey<'a> + KeyDeserialize,
+    <K::Suffix as KeyDeserialize>::Output: 'static,
+    V: Serialize + DeserializeOwned,
+{
+    map.prefix(prefix)
+        .range(storage, None, None, Order::Ascending)
+        .next()
+        .transpose()
+}
+
+/// Entry with the largest suffix under `prefix`
+pub fn last_in_prefix<'a, K, V>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, V>,
+    prefix: K::Prefix,
+) -> StdResult<Option<(<K::Suffix as KeyDeserialize>::Output, V)>>
+where
+    K: PrimaryKey<'a>,
+    K::Prefix: Prefixer<'a>,
+    K::Suffix: PrimaryKey<'a> + KeyDeserialize,
+    <K::Suffix as KeyDeserialize>::Output: 'static,
+    V: Serialize + DeserializeOwned,
+{
+    map.prefix(prefix)
+        .range(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+}
+
+/// The `n` entries with the largest keys, largest first
+pub fn top_n<'a, K, V>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, V>,
+    n: usize,
+) -> StdResult<Vec<(K::Output, V)>>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    V: Serialize + DeserializeOwned,
+{
+    map.range(storage, None, None, Order::Descending)
+        .take(n)
+        .collect()
+}
+
+/// The `n` entries with the smallest keys, smallest first
+pub fn bottom_n<'a, K, V>(
+    storage: &dyn Storage,
+    map: &Map<'a, K, V>,
+    n: usize,
+) -> StdResult<Vec<(K::Output, V)>>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    V: Serialize + DeserializeOwned,
+{
+    map.range(storage, None, None, Order::Ascending)
+        .take(n)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{bottom_n, first, first_in_prefix, last, last_in_prefix, top_n, Map};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn first_and_last() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, String> = Map::new("test_map");
+
+        assert_eq!(first(deps.as_ref().storage, &test_map).unwrap(), None);
+        assert_eq!(last(deps.as_ref().storage, &test_map).unwrap(), None);
+
+        for i in 10..50 {
+            test_map
+                .save(deps.as_mut().storage, i, &format!("string-{}", i))
+                .unwrap();
+        }
+
+        assert_eq!(
+            first(deps.as_ref().storage, &test_map).unwrap(),
+            Some((10, "string-10".to_string()))
+        );
+        assert_eq!(
+            last(deps.as_ref().storage, &test_map).unwrap(),
+            Some((49, "string-49".to_string()))
+        );
+    }
+
+    #[test]
+    fn first_and_last_in_prefix() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<(u8, u32), u32> = Map::new("test_map");
+
+        for i in 0..30 {
+            test_map
+                .save(deps.as_mut().storage, (i % 3, i as u32), &(i as u32))
+                .unwrap();
+        }
+
+        assert_eq!(
+            first_in_prefix(deps.as_ref().storage, &test_map, 1).unwrap(),
+            Some((1, 1))
+        );
+        assert_eq!(
+            last_in_prefix(deps.as_ref().storage, &test_map, 1).unwrap(),
+            Some((28, 28))
+        );
+        assert_eq!(
+            last_in_prefix(deps.as_ref().storage, &test_map, 7).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn top_and_bottom_n() {
+        let mut deps = mock_dependencies();
+        // Leaderboard keyed by (score, player) so ties stay unique
+        let leaderboard: Map<(u64, String), ()> = Map::new("leaderboard");
+
+        for (score, player) in [(50, "alice"), (80, "bob"), (80, "carol"), (10, "dave")] {
+            leaderboard
+                .save(deps.as_mut().storage, (score, player.to_string()), &())
+                .unwrap();
+        }
+
+        let top: Vec<_> = top_n(deps.as_ref().storage, &leaderboard, 3)
+            .unwrap()
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(
+            top,
+            vec![
+                (80, "carol".to_string()),
+                (80, "bob".to_string()),
+                (50, "alice".to_string())
+            ]
+        );
+
+        let bottom = bottom_n(deps.as_ref().storage, &leaderboard, 10).unwrap();
+        assert_eq!(bottom.len(), 4);
+        assert_eq!(bottom.first().unwrap().0, (10, "dave".to_string()));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{Map, PaginatedQuery};
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{to_json_binary, Binary, StdResult, Storage};
+
+/// Paginates `map` with whichever page type `page` is and json encodes the result,
+/// the function behind [`paginate!`]
+#[doc(alias = "into_json_page")]
+pub fn paginate_json<'a, Query, Key, Value, Data, Function>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, Key, Value>,
+    page: Query,
+    transform: Function,
+) -> StdResult<Binary>
+where
+    Query: PaginatedQuery<'a, Key, Value, Data>,
+    Query::POutput: Serialize,
+    Function: FnOnce(&Query::FuncKey, Value) -> Data + Copy,
+{
+    to_json_binary(&page.into_pagination(storage, map, transform)?)
+}
+
+/// Answers a query handler's page request in one line, returning `StdResult<Binary>`.
+/// `Page`, `PrefixPage` or any other [`PaginatedQuery`] is picked from the page's type
+/// and the values are returned as is when no transform is given
+///
+/// ```ignore
+/// QueryMsg::Balances { page } => paginate!(BALANCES, deps.storage, page),
+/// QueryMsg::History { page } => paginate!(HISTORY, deps.storage, page, |ts, amount| (*ts, amount)),
+/// ```
+#[macro_export]
+macro_rules! paginate {
+    ($map:expr, $storage:expr, $page:expr $(,)?) => {
+        $crate::paginate_json($storage, &$map, $page, |_, value| value)
+    };
+    ($map:expr, $storage:expr, $page:expr, $transform:expr $(,)?) => {
+        $crate::paginate_json($storage, &$map, $page, $transform)
+    };
+}
+
+/// Declares concrete, non generic page responses for schema tooling such as ts-codegen
+/// that handles generics poorly. Each struct has the fields of [`NextPage`](crate::NextPage)
+/// and converts from it. `qty` can be renamed to match a frontend's naming, and serde
+/// attributes such as `#[serde(rename_all = "camelCase")]` are forwarded to the struct
+///
+/// ```ignore
+/// declare_page_responses! {
+///     pub struct AccountsPage = NextPage<AccountInfo, String>;
+///     #[serde(rename_all = "camelCase")]
+///     pub struct OrdersPage = NextPage<Order, (Addr, u64)> { qty: count };
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_page_responses {
+    ($(
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident = NextPage<$data:ty, $next:ty> $({ qty: $qty:ident })?;
+    )*) => {
+        $(
+            $crate::declare_page_responses!(
+                @struct [$(#[$meta])*] $vis $name, $data, $next, [$($qty)?]
+            );
+        )*
+    };
+    (@struct [$($attr:tt)*] $vis:vis $name:ident, $data:ty, $next:ty, []) => {
+        $crate::declare_page_responses!(@struct [$($attr)*] $vis $name, $data, $next, [qty]);
+    };
+    (@struct [$($attr:tt)*] $vis:vis $name:ident, $data:ty, $next:ty, [$qty:ident]) => {
+        $($attr)*
+        #[$crate::__private::cw_serde]
+        $vis struct $name {
+            pub data: Vec<$data>,
+            pub next: Option<$next>,
+            pub $qty: usize,
+            #[serde(default)]
+            pub scanned: usize,
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub errors: Vec<$crate::PageError>,
+        }
+
+        impl From<$crate::NextPage<$data, $next>> for $name {
+            fn from(page: $crate::NextPage<$data, $next>) -> Self {
+                $name {
+                    data: page.data,
+                    next: page.next,
+                    $qty: page.qty,
+                    scanned: page.scanned,
+                    errors: page.errors,
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, NextPage, Page, PrefixPage};
+    use cosmwasm_schema::schema_for;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{from_json, to_json_string};
+
+    const TEST_MAP: Map<'static, (u8, u32), u32> = Map::new("TEST_MAP");
+
+    #[test]
+    fn paginate() {
+        let mut deps = mock_dependencies();
+
+        for i in 0..20 {
+            TEST_MAP
+                .save(deps.as_mut().storage, ((i % 2) as u8, i), &i)
+                .unwrap();
+        }
+
+        let page: Page<3, (u8, u32)> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = paginate!(TEST_MAP, deps.as_ref().storage, page).unwrap();
+        let res: NextPage<u32, (u8, u32)> = from_json(res).unwrap();
+        assert_eq!(res.data, vec![0, 2, 4]);
+        assert_eq!(res.next, Some((0, 4)));
+
+        let page: PrefixPage<3, _, _, _> = PrefixPage {
+            prefix: 1,
+            start: Some(5),
+            qty: Some(2),
+        };
+        let res = paginate!(TEST_MAP, deps.as_ref().storage, page, |k, v| k + v).unwrap();
+        let res: NextPage<u32, u32> = from_json(res).unwrap();
+        assert_eq!(res.data, vec![14, 18]);
+        assert_eq!(res.next, Some(9));
+    }
+
+    declare_page_responses! {
+        /// Values keyed by their stringified key
+        pub struct StringPage = NextPage<u32, String>;
+        #[serde(rename_all = "camelCase")]
+        pub struct CountPage = NextPage<u32, String> { qty: count };
+    }
+
+    #[test]
+    fn page_responses() {
+        let page = NextPage {
+            data: vec![1, 2],
+            next: Some("b".to_string()),
+            qty: 2,
+            scanned: 3,
+            errors: vec![],
+        };
+        let json = to_json_string(&page).unwrap();
+
+        let res = StringPage::from(page);
+        assert_eq!(res.data, vec![1, 2]);
+        assert_eq!(res.next, Some("b".to_string()));
+        assert_eq!(res.scanned, 3);
+        assert_eq!(to_json_string(&res).unwrap(), json);
+        assert_eq!(
+            schema_for!(StringPage).schema.metadata.unwrap().title,
+            Some("StringPage".to_string())
+        );
+    }
+
+    #[test]
+    fn renamed_page_responses() {
+        let page = NextPage {
+            data: vec![1u32],
+            next: Some("a".to_string()),
+            qty: 1,
+            scanned: 4,
+            errors: vec![],
+        };
+
+        let res = CountPage::from(page);
+        assert_eq!(res.count, 1);
+        assert_eq!(
+            to_json_string(&res).unwrap(),
+            r#"{"data":[1],"next":"a","count":1,"scanned":4}"#
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use crate::error::validate_qty;
+use crate::{Map, PaginatedQuery, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_json, to_json_binary, Binary, Storage};
+
+/// Page request against the map the contract exposes as `map`, `page` is the json
+/// encoded `Page` or `PrefixPage` that map expects
+#[cw_serde]
+pub struct SubQuery {
+    pub map: String,
+    pub page: Binary,
+}
+
+pub type DefaultMultiQuery = MultiQuery<10>;
+
+/// Several independent page requests over different maps answered by a single query,
+/// cutting round trips for dashboards. At most `MAX_QUERIES` sub queries are accepted
+#[cw_serde]
+pub struct MultiQuery<const MAX_QUERIES: usize> {
+    pub queries: Vec<SubQuery>,
+}
+
+impl<const MAX_QUERIES: usize> MultiQuery<MAX_QUERIES> {
+    /// Runs every sub query through `handler`, the responses keep the request order
+    pub fn into_responses<Handler>(self, mut handler: Handler) -> Result<Vec<Binary>, QueryError>
+    where
+        Handler: FnMut(&str, &Binary) -> Result<Binary, QueryError>,
+    {
+        validate_qty(Some(self.queries.len()), MAX_QUERIES)?;
+        self.queries
+            .iter()
+            .map(|query| handler(&query.map, &query.page))
+            .collect()
+    }
+}
+
+/// Decodes `page` as `Query` and json encodes the resulting `NextPage`, meant to be
+/// called from a [`MultiQuery`] handler
+pub fn paginate_binary<'a, Query, Key, Value, Data, Function>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, Key, Value>,
+    page: &Binary,
+    transform: Function,
+) -> Result<Binary, QueryError>
+where
+    Query: PaginatedQuery<'a, Key, Value, Data> + DeserializeOwned,
+    Query::POutput: Serialize,
+    Function: FnOnce(&Query::FuncKey, Value) -> Data + Copy,
+{
+    let query: Query = from_json(page)?;
+    let res = query.into_pagination(storage, map, transform)?;
+    Ok(to_json_binary(&res)?)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        paginate_binary, Map, MultiQuery, NextPage, Page, PrefixPage, QueryError, SubQuery,
+    };
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{from_json, to_json_binary};
+
+    type HoldingsPage<'a> = PrefixPage<'a, 3, (String, u32), String, u32>;
+
+    #[test]
+    fn into_responses() {
+        let mut deps = mock_dependencies();
+        let prices: Map<u8, u8> = Map::new("prices");
+        let holdings: Map<(String, u32), u64> = Map::new("holdings");
+
+        for i in 0..10 {
+            prices.save(deps.as_mut().storage, i, &(i * 2)).unwrap();
+            holdings
+                .save(deps.as_mut().storage, ("alice".to_string(), i as u32), &10)
+                .unwrap();
+        }
+
+        let query: MultiQuery<2> = MultiQuery {
+            queries: vec![
+                SubQuery {
+                    map: "holdings".to_string(),
+                    page: to_json_binary(&HoldingsPage {
+                        prefix: "alice".to_string(),
+                        start: Some(5),
+                        qty: None,
+                    })
+                    .unwrap(),
+                },
+                SubQuery {
+                    map: "prices".to_string(),
+                    page: to_json_binary(&Page::<3, u8> {
+                        start: None,
+                        qty: Some(2),
+                    })
+                    .unwrap(),
+                },
+            ],
+        };
+
+        let storage = deps.as_ref().storage;
+        let res = query
+            .clone()
+            .into_responses(|map, page| match map {
+                "prices" => {
+                    paginate_binary::<Page<3, u8>, _, _, _, _>(storage, &prices, page, |_, v| v)
+                }
+                "holdings" => {
+                    paginate_binary::<HoldingsPage, _, _, _, _>(storage, &holdings, page, |k, _| *k)
+                }
+                name => Err(QueryError::UnknownMap {
+                    name: name.to_string(),
+                }),
+            })
+            .unwrap();
+
+        assert_eq!(res.len(), 2);
+        let holdings_page: NextPage<u32, u32> = from_json(&res[0]).unwrap();
+        assert_eq!(holdings_page.data, vec![6, 7, 8]);
+        let prices_page: NextPage<u8, u8> = from_json(&res[1]).unwrap();
+        assert_eq!(prices_page.data, vec![0, 2]);
+
+        let mut oversized = query.clone();
+        oversized.queries.push(oversized.queries[0].clone());
+        assert_eq!(
+            oversized.into_responses(|_, page| Ok(page.clone())),
+            Err(QueryError::LimitExceeded {
+                requested: 3,
+                max: 2
+            })
+        );
+
+        let err = query
+            .into_responses(|map, _| {
+                Err(QueryError::UnknownMap {
+                    name: map.to_string(),
+                })
+            })
+            .unwrap_err();
+        assert_eq!(
+            err,
+            QueryError::UnknownMap {
+                name: "holdings".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,421 @@
+use crate::{namespace, Map, Page, PaginatedMutation};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{
+    from_json, to_json_string, to_json_vec, Attribute, Empty, Order, Record, StdError, StdResult,
+    Storage,
+};
+use cw_storage_plus::{
+    range_with_prefix, Bound, Key, KeyDeserialize, Prefix, PrimaryKey, RawBound,
+};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+
+/// Progress of a paginated storage mutation
+#[cw_serde]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+pub struct MutatedPage<K> {
+    pub next: Option<K>,
+    pub qty: usize,
+}
+
+impl<K: Serialize> MutatedPage<K> {
+    /// Summarizes the progress as `{prefix}_qty` and `{prefix}_next` attributes,
+    /// the cursor is json encoded
+    pub fn into_attributes(&self, prefix: &str) -> StdResult<Vec<Attribute>> {
+        Ok(vec![
+            Attribute::new(format!("{}_qty", prefix), self.qty.to_string()),
+            Attribute::new(format!("{}_next", prefix), to_json_string(&self.next)?),
+        ])
+    }
+}
+
+/// Page resolved into its raw storage prefix, shared by the [`PaginatedMutation`] implementations
+pub(crate) struct RawPage {
+    pub prefix: Vec<u8>,
+    pub start: Option<Vec<u8>>,
+    pub limit: usize,
+}
+
+impl RawPage {
+    pub fn new(namespace: &[u8], sub_names: &[Key], start: Option<Vec<u8>>, limit: usize) -> Self {
+        let prefix: Prefix<Vec<u8>, Empty> = Prefix::new(namespace, sub_names);
+        RawPage {
+            prefix: prefix.to_vec(),
+            start,
+            limit,
+        }
+    }
+
+    fn entries(&self, storage: &dyn Storage) -> Vec<Record> {
+        range_with_prefix(
+            storage,
+            &self.prefix,
+            self.start.clone().map(RawBound::Exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(self.limit)
+        .collect()
+    }
+
+    fn full_key(&self, raw_key: &[u8]) -> Vec<u8> {
+        [self.prefix.as_slice(), raw_key].concat()
+    }
+
+    pub fn delete<K: KeyDeserialize>(
+        &self,
+        storage: &mut dyn Storage,
+    ) -> StdResult<MutatedPage<K::Output>> {
+        let entries = self.entries(storage);
+        let qty = entries.len();
+        let end = match entries.last() {
+            Some((raw_key, _)) => Some(K::from_slice(raw_key)?),
+            None => None,
+        };
+
+        for (raw_key, _) in entries {
+            storage.remove(&self.full_key(&raw_key));
+        }
+
+        Ok(MutatedPage { next: end, qty })
+    }
+
+    pub fn update<K, Value, Function>(
+        &self,
+        storage: &mut dyn Storage,
+        mut action: Function,
+    ) -> StdResult<MutatedPage<K::Output>>
+    where
+        K: KeyDeserialize,
+        Value: Serialize + DeserializeOwned,
+        Function: FnMut(&K::Output, Value) -> StdResult<Option<Value>>,
+    {
+        let entries = self.entries(storage);
+        let qty = entries.len();
+        let mut end = None;
+
+        for (raw_key, raw_value) in entries {
+            let key = K::from_slice(&raw_key)?;
+            match action(&key, from_json(&raw_value)?)? {
+                Some(value) => storage.set(&self.full_key(&raw_key), &to_json_vec(&value)?),
+                None => storage.remove(&self.full_key(&raw_key)),
+            }
+            end = Some(key);
+        }
+
+        Ok(MutatedPage { next: end, qty })
+    }
+
+    pub fn rekey<'n, K, Value, NewKey, NewValue, Function>(
+        &self,
+        storage: &mut dyn Storage,
+        new_map: &Map<'n, NewKey, NewValue>,
+        delete_old: bool,
+        mut transform: Function,
+    ) -> StdResult<MutatedPage<K::Output>>
+    where
+        K: KeyDeserialize,
+        Value: DeserializeOwned,
+        NewKey: PrimaryKey<'n>,
+        NewValue: Serialize + DeserializeOwned,
+        Function: FnMut(&K::Output, Value) -> (NewKey, NewValue),
+    {
+        let entries = self.entries(storage);
+        let qty = entries.len();
+        let mut end = None;
+
+        for (raw_key, raw_value) in entries {
+            let key = K::from_slice(&raw_key)?;
+            let (new_key, new_value) = transform(&key, from_json(&raw_value)?);
+
+            if delete_old {
+                storage.remove(&self.full_key(&raw_key));
+            }
+            new_map.save(storage, new_key, &new_value)?;
+
+            end = Some(key);
+        }
+
+        Ok(MutatedPage { next: end, qty })
+    }
+}
+
+/// Copies a page of entries into `new_map` under the keys returned by `transform`,
+/// removing the old entries when `delete_old` is set
+pub fn rekey_page<'a, const LIMIT: usize, OldKey, OldValue, NewKey, NewValue, Function>(
+    storage: &mut dyn Storage,
+    old_map: &Map<'a, OldKey, OldValue>,
+    new_map: &Map<'a, NewKey, NewValue>,
+    page: Page<LIMIT, OldKey>,
+    delete_old: bool,
+    transform: Function,
+) -> StdResult<MutatedPage<OldKey::Output>>
+where
+    OldKey: PrimaryKey<'a> + KeyDeserialize,
+    OldValue: Serialize + DeserializeOwned,
+    NewKey: PrimaryKey<'a>,
+    NewValue: Serialize + DeserializeOwned,
+    Function: FnMut(&OldKey::Output, OldValue) -> (NewKey, NewValue),
+{
+    page.rekey(storage, old_map, new_map, delete_old, transform)
+}
+
+/// Removes up to `limit` entries after `start` and before `end`, both bounds are exclusive
+pub fn delete_range<'a, Key, Value>(
+    storage: &mut dyn Storage,
+    map: &Map<'a, Key, Value>,
+    start: Option<Key>,
+    end: Option<Key>,
+    limit: usize,
+) -> StdResult<MutatedPage<Key::Output>>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    Value: Serialize + DeserializeOwned,
+{
+    let keys: Vec<_> = map
+        .keys_raw(
+            storage,
+            start.map(|s| Bound::Exclusive((s, PhantomData))),
+            end.map(|e| Bound::Exclusive((e, PhantomData))),
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect();
+
+    let namespace: Prefix<Vec<u8>, Empty> = Prefix::new(namespace(map), &[]);
+    let qty = keys.len();
+    let end = match keys.last() {
+        Some(raw_key) => Some(Key::from_slice(raw_key)?),
+        None => None,
+    };
+
+    for raw_key in keys {
+        storage.remove(&[namespace.as_ref(), raw_key.as_slice()].concat());
+    }
+
+    Ok(MutatedPage { next: end, qty })
+}
+
+/// How [`write_page`] handles keys that are already stored
+#[cw_serde]
+pub enum WritePolicy {
+    Overwrite,
+    SkipExisting,
+    ErrorOnExisting,
+}
+
+#[cw_serde]
+pub enum WriteOutcome {
+    Written,
+    Overwritten,
+    Skipped,
+}
+
+/// Saves every entry following the given policy, `ErrorOnExisting` checks every key
+/// before writing so nothing is saved when it fails, keys repeated within `entries`
+/// count as existing
+pub fn write_page<'a, Key, Value>(
+    storage: &mut dyn Storage,
+    map: &Map<'a, Key, Value>,
+    entries: Vec<(Key, Value)>,
+    policy: WritePolicy,
+) -> StdResult<Vec<WriteOutcome>>
+where
+    Key: PrimaryKey<'a>,
+    Value: Serialize + DeserializeOwned,
+{
+    if policy == WritePolicy::ErrorOnExisting {
+        let mut seen = HashSet::with_capacity(entries.len());
+        for (key, _) in &entries {
+            if !seen.insert(key.joined_key()) || map.has(storage, key.clone()) {
+                return Err(StdError::generic_err("Key already exists"));
+            }
+        }
+    }
+
+    let mut outcomes = Vec::with_capacity(entries.len());
+    for (key, value) in entries {
+        let outcome = match (map.has(storage, key.clone()), &policy) {
+            (false, _) => WriteOutcome::Written,
+            (true, WritePolicy::SkipExisting) => WriteOutcome::Skipped,
+            (true, _) => WriteOutcome::Overwritten,
+        };
+
+        if outcome != WriteOutcome::Skipped {
+            map.save(storage, key, &value)?;
+        }
+        outcomes.push(outcome);
+    }
+
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{delete_range, rekey_page, write_page, Map, Page, WriteOutcome, WritePolicy};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{Attribute, Order};
+
+    #[test]
+    fn rekey_page_moves_entries() {
+        let mut deps = mock_dependencies();
+        let old_map: Map<u8, String> = Map::new("old_map");
+        let new_map: Map<(String, u8), u16> = Map::new("new_map");
+
+        for i in 0..50 {
+            old_map
+                .save(deps.as_mut().storage, i, &format!("owner-{}", i % 2))
+                .unwrap();
+        }
+
+        let page: Page<20, _> = Page {
+            start: None,
+            qty: None,
+        };
+
+        let res = rekey_page(
+            deps.as_mut().storage,
+            &old_map,
+            &new_map,
+            page,
+            true,
+            |k, v| ((v, *k), *k as u16),
+        )
+        .unwrap();
+
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(19));
+        assert!(!old_map.has(deps.as_ref().storage, 19));
+        assert!(old_map.has(deps.as_ref().storage, 20));
+        assert_eq!(
+            new_map
+                .load(deps.as_ref().storage, ("owner-1".to_string(), 19))
+                .unwrap(),
+            19
+        );
+
+        let page: Page<20, _> = Page {
+            start: res.next,
+            qty: Some(40),
+        };
+
+        let res = rekey_page(
+            deps.as_mut().storage,
+            &old_map,
+            &new_map,
+            page,
+            false,
+            |k, v| ((v, *k), *k as u16),
+        )
+        .unwrap();
+
+        assert_eq!(res.qty, 30);
+        assert_eq!(res.next, Some(49));
+        assert!(old_map.has(deps.as_ref().storage, 49));
+        assert_eq!(
+            new_map
+                .prefix("owner-0".to_string())
+                .range(deps.as_ref().storage, None, None, Order::Ascending)
+                .count(),
+            25
+        );
+    }
+
+    #[test]
+    fn delete_range_removes_entries() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let res = delete_range(deps.as_mut().storage, &test_map, Some(9), Some(50), 20).unwrap();
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(29));
+        assert_eq!(
+            res.into_attributes("pruned").unwrap(),
+            vec![
+                Attribute::new("pruned_qty", "20"),
+                Attribute::new("pruned_next", "29")
+            ]
+        );
+        assert!(test_map.has(deps.as_ref().storage, 9));
+        assert!(!test_map.has(deps.as_ref().storage, 10));
+        assert!(test_map.has(deps.as_ref().storage, 30));
+
+        let res = delete_range(deps.as_mut().storage, &test_map, res.next, Some(50), 50).unwrap();
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(49));
+        assert!(test_map.has(deps.as_ref().storage, 50));
+
+        let res = delete_range(deps.as_mut().storage, &test_map, res.next, Some(50), 50).unwrap();
+        assert_eq!(res.qty, 0);
+        assert_eq!(res.next, None);
+        assert_eq!(
+            test_map
+                .keys(deps.as_ref().storage, None, None, Order::Ascending)
+                .count(),
+            60
+        );
+    }
+
+    #[test]
+    fn write_page_policies() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        test_map.save(deps.as_mut().storage, 1, &1).unwrap();
+
+        let res = write_page(
+            deps.as_mut().storage,
+            &test_map,
+            vec![(0, 10), (1, 10), (2, 10)],
+            WritePolicy::SkipExisting,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            vec![
+                WriteOutcome::Written,
+                WriteOutcome::Skipped,
+                WriteOutcome::Written
+            ]
+        );
+        assert_eq!(test_map.load(deps.as_ref().storage, 1).unwrap(), 1);
+
+        let res = write_page(
+            deps.as_mut().storage,
+            &test_map,
+            vec![(1, 20), (3, 20)],
+            WritePolicy::Overwrite,
+        )
+        .unwrap();
+        assert_eq!(res, vec![WriteOutcome::Overwritten, WriteOutcome::Written]);
+        assert_eq!(test_map.load(deps.as_ref().storage, 1).unwrap(), 20);
+
+        let res = write_page(
+            deps.as_mut().storage,
+            &test_map,
+            vec![(4, 30), (3, 30)],
+            WritePolicy::ErrorOnExisting,
+        );
+        assert!(res.is_err());
+        assert!(!test_map.has(deps.as_ref().storage, 4));
+
+        // The batch can't write the same key twice either
+        let res = write_page(
+            deps.as_mut().storage,
+            &test_map,
+            vec![(5, 40), (6, 40), (5, 41)],
+            WritePolicy::ErrorOnExisting,
+        );
+        assert!(res.is_err());
+        assert!(!test_map.has(deps.as_ref().storage, 5));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{dynamic_map, NextPage, Page, PaginatedQuery};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+
+pub type DefaultNamespacedPage<K> = NamespacedPage<50, K>;
+
+/// Page over a map living under a namespace built at runtime, the usual workaround for
+/// nested maps e.g. `format!("orders:{owner}")`. The map only lives for the call, so keys
+/// must be owned types
+#[cw_serde]
+pub struct NamespacedPage<const LIMIT: usize, K> {
+    pub namespace: String,
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key> NamespacedPage<LIMIT, Key> {
+    pub fn into_namespaced_pagination<Value, Data, Function>(
+        self,
+        storage: &dyn Storage,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: for<'k> PrimaryKey<'k> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let map = dynamic_map::<Key, Value>(&self.namespace);
+        let page: Page<LIMIT, Key> = Page {
+            start: self.start,
+            qty: self.qty,
+        };
+        page.into_pagination(storage, &map, transform)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{dynamic_map, Map, NamespacedPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_namespaced_pagination() {
+        let mut deps = mock_dependencies();
+
+        for owner in ["alice", "bob"] {
+            let namespace = format!("orders:{}", owner);
+            let orders: Map<u64, String> = dynamic_map(&namespace);
+            for id in 0..10 {
+                orders
+                    .save(deps.as_mut().storage, id, &format!("{}-{}", owner, id))
+                    .unwrap();
+            }
+        }
+
+        let query: NamespacedPage<3, u64> = NamespacedPage {
+            namespace: "orders:bob".to_string(),
+            start: Some(4),
+            qty: None,
+        };
+        let res = query
+            .into_namespaced_pagination(deps.as_ref().storage, |_, v: String| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["bob-5", "bob-6", "bob-7"]);
+        assert_eq!(res.next, Some(7));
+
+        let query: NamespacedPage<3, u64> = NamespacedPage {
+            namespace: "orders:carol".to_string(),
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_namespaced_pagination(deps.as_ref().storage, |_, v: String| v)
+            .unwrap();
+        assert_eq!(res.qty, 0);
+    }
+}
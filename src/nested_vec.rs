@@ -0,0 +1,142 @@
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultNestedVecPage<K> = NestedVecPage<50, K>;
+
+/// Page over maps whose values are growing vectors, such as per user histories, at item
+/// granularity instead of value granularity. The cursor is the key and the index of the
+/// last returned item within its vector
+#[cw_serde]
+pub struct NestedVecPage<const LIMIT: usize, K> {
+    pub start: Option<(K, u32)>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key> NestedVecPage<LIMIT, Key> {
+    pub fn into_nested_vec_pagination<'a, Item, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Vec<Item>>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, (Key::Output, u32)>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Item: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, u32, Item) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        // The cursor's vector is resumed after its index, unless the entry was removed
+        // in which case the range already starts at the following key
+        let (bound, mut skip) = match self.start {
+            Some((key, index)) => {
+                let skip = map.has(storage, key.clone()).then_some(index as usize + 1);
+                (Some(Bound::Inclusive((key, PhantomData))), skip)
+            }
+            None => (None, None),
+        };
+        let range = map.range(storage, bound, None, Order::Ascending);
+        let mut data = vec![];
+        let mut end = None;
+
+        for item in range {
+            if data.len() >= limit {
+                break;
+            }
+
+            let (key, items) = item?;
+            let mut last = None;
+            let items = items.into_iter().enumerate().skip(skip.take().unwrap_or(0));
+            for (index, value) in items.take(limit - data.len()) {
+                data.push(transform(&key, index as u32, value));
+                last = Some(index as u32);
+            }
+            if let Some(last) = last {
+                end = Some((key, last));
+            }
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, NestedVecPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_nested_vec_pagination() {
+        let mut deps = mock_dependencies();
+        let history: Map<&str, Vec<u32>> = Map::new("history");
+
+        history
+            .save(deps.as_mut().storage, "alice", &(0..5).collect())
+            .unwrap();
+        history
+            .save(deps.as_mut().storage, "bob", &vec![10, 11])
+            .unwrap();
+        history
+            .save(deps.as_mut().storage, "carol", &(20..23).collect())
+            .unwrap();
+
+        let query: NestedVecPage<4, &str> = NestedVecPage {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_nested_vec_pagination(deps.as_ref().storage, &history, |_, _, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![0, 1, 2, 3]);
+        assert_eq!(res.next, Some(("alice".to_string(), 3)));
+
+        let query: NestedVecPage<4, &str> = NestedVecPage {
+            start: Some(("alice", 3)),
+            qty: None,
+        };
+        let res = query
+            .into_nested_vec_pagination(deps.as_ref().storage, &history, |k, i, v| {
+                format!("{}-{}-{}", k, i, v)
+            })
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec!["alice-4-4", "bob-0-10", "bob-1-11", "carol-0-20"]
+        );
+        assert_eq!(res.next, Some(("carol".to_string(), 0)));
+
+        // A removed cursor entry resumes at the following key
+        history.remove(deps.as_mut().storage, "bob");
+        let query: NestedVecPage<4, &str> = NestedVecPage {
+            start: Some(("bob", 0)),
+            qty: None,
+        };
+        let res = query
+            .into_nested_vec_pagination(deps.as_ref().storage, &history, |_, _, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![20, 21, 22]);
+        assert_eq!(res.next, Some(("carol".to_string(), 2)));
+
+        let query: NestedVecPage<4, &str> = NestedVecPage {
+            start: res.next.as_ref().map(|(k, i)| (k.as_str(), *i)),
+            qty: None,
+        };
+        let res = query
+            .into_nested_vec_pagination(deps.as_ref().storage, &history, |_, _, v| v)
+            .unwrap();
+        assert!(res.data.is_empty());
+        assert_eq!(res.next, None);
+    }
+}
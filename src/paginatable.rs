@@ -0,0 +1,215 @@
+use crate::{paginate_iter, Map, NextPage, Page, PrefixPage};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+/// Boxed entries returned by the container hooks
+pub type PageIter<'a, K, V> = Box<dyn Iterator<Item = StdResult<(K, V)>> + 'a>;
+
+/// Storage container a [`Page`] can run over, implemented for `Map` so wrappers and
+/// third party containers can plug into the same pagination
+pub trait Paginatable<'a> {
+    /// Key the page starts after
+    type Key;
+    /// Key handed to the transform and returned as the cursor
+    type Output;
+    type Value;
+
+    /// Entries after `start` in the given order
+    fn page_range(
+        &'a self,
+        storage: &'a dyn Storage,
+        start: Option<Self::Key>,
+        order: Order,
+    ) -> PageIter<'a, Self::Output, Self::Value>;
+}
+
+/// [`Paginatable`] for containers with composite keys, runs [`PrefixPage`]s
+pub trait PrefixPaginatable<'a> {
+    type Prefix;
+    type Suffix;
+    /// Suffix handed to the transform and returned as the cursor
+    type Output;
+    type Value;
+
+    /// Entries under `prefix` whose suffix comes after `start` in the given order
+    fn page_prefix_range(
+        &'a self,
+        storage: &'a dyn Storage,
+        prefix: Self::Prefix,
+        start: Option<Self::Suffix>,
+        order: Order,
+    ) -> PageIter<'a, Self::Output, Self::Value>;
+}
+
+impl<'a, K, V> Paginatable<'a> for Map<'a, K, V>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: 'static,
+    V: Serialize + DeserializeOwned + 'a,
+{
+    type Key = K;
+    type Output = K::Output;
+    type Value = V;
+
+    fn page_range(
+        &'a self,
+        storage: &'a dyn Storage,
+        start: Option<K>,
+        order: Order,
+    ) -> PageIter<'a, K::Output, V> {
+        let start = start.map(|s| Bound::Exclusive((s, PhantomData)));
+        match order {
+            Order::Ascending => self.range(storage, start, None, order),
+            Order::Descending => self.range(storage, None, start, order),
+        }
+    }
+}
+
+impl<'a, K, V> PrefixPaginatable<'a> for Map<'a, K, V>
+where
+    K: PrimaryKey<'a>,
+    K::Suffix: PrimaryKey<'a> + KeyDeserialize,
+    <K::Suffix as KeyDeserialize>::Output: 'static,
+    V: Serialize + DeserializeOwned + 'a,
+{
+    type Prefix = K::Prefix;
+    type Suffix = K::Suffix;
+    type Output = <K::Suffix as KeyDeserialize>::Output;
+    type Value = V;
+
+    fn page_prefix_range(
+        &'a self,
+        storage: &'a dyn Storage,
+        prefix: K::Prefix,
+        start: Option<K::Suffix>,
+        order: Order,
+    ) -> PageIter<'a, Self::Output, V> {
+        let start = start.map(|s| Bound::Exclusive((s, PhantomData)));
+        match order {
+            Order::Ascending => self.prefix(prefix).range(storage, start, None, order),
+            Order::Descending => self.prefix(prefix).range(storage, None, start, order),
+        }
+    }
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Same as `into_pagination` over any [`Paginatable`] container
+    pub fn into_container_pagination<'a, C, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        container: &'a C,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, C::Output>>
+    where
+        C: Paginatable<'a, Key = Key>,
+        C::Output: Clone,
+        Function: FnOnce(&C::Output, C::Value) -> Data + Copy,
+    {
+        let range = container
+            .page_range(storage, self.start, Order::Ascending)
+            .map(|item| item.map(|(key, value)| (key.clone(), transform(&key, value))));
+        paginate_iter(range, self.qty.unwrap_or(LIMIT), Clone::clone)
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Prefix, Suffix> PrefixPage<'a, LIMIT, Key, Prefix, Suffix>
+where
+    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+    Suffix: PrimaryKey<'a> + KeyDeserialize + Serialize + Clone,
+    Prefix: Serialize,
+{
+    /// Same as `into_pagination` over any [`PrefixPaginatable`] container
+    pub fn into_container_pagination<C, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        container: &'a C,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, C::Output>>
+    where
+        C: PrefixPaginatable<'a, Prefix = Prefix, Suffix = Suffix>,
+        C::Output: Clone,
+        Function: FnOnce(&C::Output, C::Value) -> Data + Copy,
+    {
+        let range = container
+            .page_prefix_range(storage, self.prefix, self.start, Order::Ascending)
+            .map(|item| item.map(|(key, value)| (key.clone(), transform(&key, value))));
+        paginate_iter(range, self.qty.unwrap_or(LIMIT), Clone::clone)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Page, PageIter, Paginatable, PrefixPage};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{Order, Storage};
+    use std::collections::BTreeMap;
+
+    /// Container kept outside of contract storage
+    struct MemoryMap(BTreeMap<u32, String>);
+
+    impl<'a> Paginatable<'a> for MemoryMap {
+        type Key = u32;
+        type Output = u32;
+        type Value = String;
+
+        fn page_range(
+            &'a self,
+            _: &'a dyn Storage,
+            start: Option<u32>,
+            order: Order,
+        ) -> PageIter<'a, u32, String> {
+            let after = move |key: &u32| match (start, order) {
+                (None, _) => true,
+                (Some(start), Order::Ascending) => *key > start,
+                (Some(start), Order::Descending) => *key < start,
+            };
+            let entries = self.0.iter().filter(move |(key, _)| after(*key));
+            let entries: Box<dyn Iterator<Item = _> + 'a> = match order {
+                Order::Ascending => Box::new(entries),
+                Order::Descending => Box::new(entries.rev()),
+            };
+            Box::new(entries.map(|(key, value)| Ok((*key, value.clone()))))
+        }
+    }
+
+    #[test]
+    fn into_container_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<(u8, u32), String> = Map::new("test_map");
+        let memory = MemoryMap((0..10).map(|i| (i, format!("value-{}", i))).collect());
+
+        for i in 0..10 {
+            test_map
+                .save(deps.as_mut().storage, (1, i), &format!("value-{}", i))
+                .unwrap();
+        }
+
+        let query: Page<3, u32> = Page::after(4);
+        let res = query
+            .into_container_pagination(deps.as_ref().storage, &memory, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["value-5", "value-6", "value-7"]);
+        assert_eq!(res.next, Some(7));
+
+        let query: PrefixPage<3, (u8, u32), u8, u32> = PrefixPage {
+            prefix: 1,
+            start: Some(4),
+            qty: None,
+        };
+        let res = query
+            .into_container_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["value-5", "value-6", "value-7"]);
+        assert_eq!(res.next, Some(7));
+
+        let query: Page<3, (u8, u32)> = Page::after((1, 8));
+        let res = query
+            .into_container_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["value-9"]);
+        assert_eq!(res.next, Some((1, 9)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::NextPage;
+use std::collections::BTreeMap;
+
+/// Splices items not yet persisted, e.g. created earlier in the same execution, into a
+/// page read from storage with [`crate::Page::into_map_pagination`]. `start` and `limit`
+/// are the ones the page was queried with. Pending items replace stored items with the
+/// same key, items past the end of a full page are left for the next one and the cursor
+/// points at the last item returned
+pub fn merge_pending<K, D>(
+    page: NextPage<(K, D), K>,
+    pending: impl IntoIterator<Item = (K, D)>,
+    start: Option<&K>,
+    limit: usize,
+) -> NextPage<(K, D), K>
+where
+    K: Ord + Clone,
+{
+    // A page shorter than the limit reached the end of the map
+    let end = if page.data.len() < limit {
+        None
+    } else {
+        page.next.clone()
+    };
+    let mut items: BTreeMap<K, D> = page.data.into_iter().collect();
+
+    for (key, data) in pending {
+        let after_start = start.is_none_or(|start| key > *start);
+        let before_end = end.as_ref().is_none_or(|end| key <= *end);
+        if after_start && before_end {
+            items.insert(key, data);
+        }
+    }
+
+    let data: Vec<_> = items.into_iter().take(limit).collect();
+    let len = data.len();
+    NextPage {
+        next: data.last().map(|(key, _)| key.clone()).or(page.next),
+        data,
+        qty: len,
+        scanned: page.scanned,
+        errors: page.errors,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{merge_pending, Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn merge_pending_page() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+
+        for i in [2, 4, 6, 8] {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let page = |start: Option<u32>| {
+            let query: Page<3, u32> = Page { start, qty: None };
+            query
+                .into_map_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+                .unwrap()
+        };
+        let pending = || vec![(1, 10), (4, 40), (5, 50), (9, 90)];
+
+        // 1 and 5 fall within the first page and push 6 to the next one
+        let res = merge_pending(page(None), pending(), None, 3);
+        assert_eq!(res.data, vec![(1, 10), (2, 2), (4, 40)]);
+        assert_eq!(res.next, Some(4));
+
+        let res = merge_pending(page(Some(4)), pending(), Some(&4), 3);
+        assert_eq!(res.data, vec![(5, 50), (6, 6), (8, 8)]);
+        assert_eq!(res.next, Some(8));
+
+        // The last page picks up everything left
+        let res = merge_pending(page(Some(8)), pending(), Some(&8), 3);
+        assert_eq!(res.data, vec![(9, 90)]);
+        assert_eq!(res.next, Some(9));
+    }
+}
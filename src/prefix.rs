@@ -1,10 +1,13 @@
-use crate::{KeysQuery, NextPage, PaginatedQuery};
+use crate::error::{deserialize_key, parse_page, validate_qty};
+use crate::{
+    namespace, raw_range, KeysIter, KeysQuery, Map, MutatedPage, NextPage, PaginatedMutation,
+    PaginatedQuery, QueryError, RawNextPage, RawPage,
+};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_schema::serde::Serialize;
 use cosmwasm_std::{Order, StdResult, Storage};
-use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
-use std::iter::Take;
+use cw_storage_plus::{Bound, KeyDeserialize, Prefixer, PrimaryKey};
 use std::marker::PhantomData;
 
 pub type DefaultPrefixPage<'a, Key, Prefix, Suffix> = PrefixPage<'a, 50, Key, Prefix, Suffix>;
@@ -12,7 +15,7 @@ pub type DefaultPrefixPage<'a, Key, Prefix, Suffix> = PrefixPage<'a, 50, Key, Pr
 pub struct PrefixPage<'a, const LIMIT: usize, Key, Prefix, Suffix>
 where
     Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
-    Suffix: PrimaryKey<'a> + KeyDeserialize + Serialize + DeserializeOwned + Clone,
+    Suffix: PrimaryKey<'a> + KeyDeserialize + Serialize + Clone,
     Prefix: Serialize,
 {
     pub prefix: Key::Prefix,
@@ -23,11 +26,11 @@ where
 impl<'a, const LIMIT: usize, Key, Prefix, Suffix, SO, Value, Data>
     PaginatedQuery<'a, Key, Value, Data> for PrefixPage<'a, LIMIT, Key, Prefix, Suffix>
 where
-    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix> + KeyDeserialize<Output = Key> + Clone,
-    Prefix: Serialize + DeserializeOwned,
-    Suffix: PrimaryKey<'a> + KeyDeserialize<Output = SO> + Serialize + DeserializeOwned + Clone,
-    SO: Clone + 'static,
-    Value: Serialize + DeserializeOwned + Clone + 'static,
+    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+    Prefix: Serialize,
+    Suffix: PrimaryKey<'a> + KeyDeserialize<Output = SO> + Serialize + Clone,
+    SO: 'static,
+    Value: Serialize + DeserializeOwned,
 {
     type POutput = NextPage<Data, Suffix::Output>;
     type FuncKey = Suffix::Output;
@@ -35,13 +38,14 @@ where
     fn into_pagination<Function>(
         self,
         storage: &'a dyn Storage,
-        map: &Map<'static, Key, Value>,
+        map: &Map<'a, Key, Value>,
         transform: Function,
     ) -> StdResult<Self::POutput>
     where
         Function: FnOnce(&Self::FuncKey, Value) -> Data + Copy,
     {
-        let mut keys = map
+        let limit = self.qty.unwrap_or(LIMIT);
+        let keys = map
             .prefix(self.prefix)
             .range(
                 storage,
@@ -49,20 +53,72 @@ where
                 None,
                 Order::Ascending,
             )
-            .take(self.qty.unwrap_or(LIMIT));
-        let mut data = vec![];
+            .take(limit);
+        // `qty` is client provided, capping at LIMIT avoids preallocating for oversized requests
+        let mut data = Vec::with_capacity(limit.min(LIMIT));
         let mut end = None;
 
-        let mut next = keys.next();
-        while let Some(key) = next {
-            let (key, value) = key?;
-            let res = transform(&key.clone(), value);
-            data.push(res);
+        for item in keys {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
 
-            next = keys.next();
-            if next.is_none() {
-                end = Some(key);
-            }
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Prefix, Suffix> PrefixPage<'a, LIMIT, Key, Prefix, Suffix>
+where
+    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+    Prefix: Prefixer<'a> + Serialize,
+    Suffix: PrimaryKey<'a> + KeyDeserialize + Serialize + DeserializeOwned + Clone,
+    Suffix::Output: Clone,
+{
+    /// Checks a client provided page, pagination itself doesn't enforce `LIMIT` on `qty`
+    pub fn validate(&self) -> Result<(), QueryError> {
+        validate_qty(self.qty, LIMIT)
+    }
+
+    /// Deserializes and validates a json encoded page
+    pub fn parse(value: impl AsRef<[u8]>) -> Result<Self, QueryError>
+    where
+        Prefix: DeserializeOwned,
+        Suffix: DeserializeOwned,
+    {
+        let page: Self = parse_page::<_, Suffix>(value.as_ref())?;
+        page.validate()?;
+        Ok(page)
+    }
+
+    /// Paginates without deserializing the values, returning the stored bytes as is
+    pub fn into_raw_pagination<Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> Result<RawNextPage<Suffix::Output>, QueryError> {
+        let range = raw_range(
+            storage,
+            namespace(map),
+            &self.prefix.prefix(),
+            self.start.map(|s| s.joined_key()),
+            Order::Ascending,
+        )
+        .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for (raw_key, raw_value) in range {
+            let key = deserialize_key::<Suffix>(raw_key)?;
+            data.push((key.clone(), raw_value.into()));
+            end = Some(key);
         }
 
         let len = data.len();
@@ -70,6 +126,8 @@ where
             data,
             next: end,
             qty: len,
+            scanned: len,
+            errors: vec![],
         })
     }
 }
@@ -77,34 +135,97 @@ where
 impl<'a, const LIMIT: usize, Key, Prefix, Suffix, SO, Value> KeysQuery<'a, Key, Value>
     for PrefixPage<'a, LIMIT, Key, Prefix, Suffix>
 where
-    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix> + KeyDeserialize<Output = Key> + Clone,
-    Prefix: Serialize + DeserializeOwned,
-    Suffix: PrimaryKey<'a> + KeyDeserialize<Output = SO> + Serialize + DeserializeOwned + Clone,
+    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+    Prefix: Serialize,
+    Suffix: PrimaryKey<'a> + KeyDeserialize<Output = SO> + Serialize + Clone,
     SO: 'static,
-    Value: Serialize + DeserializeOwned + Clone + 'static,
+    Value: Serialize + DeserializeOwned + 'a,
 {
     type KOutput = Suffix::Output;
     fn keys(
         self,
         storage: &'a dyn Storage,
-        map: &Map<'static, Key, Value>,
-    ) -> Take<Box<dyn Iterator<Item = StdResult<Self::KOutput>> + 'a>> {
-        map.prefix(self.prefix)
-            .keys(
-                storage,
-                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
-                None,
-                Order::Ascending,
-            )
-            .take(self.qty.unwrap_or(LIMIT))
+        map: &Map<'a, Key, Value>,
+    ) -> KeysIter<'a, Self::KOutput> {
+        let keys = map.prefix(self.prefix).keys(
+            storage,
+            self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+        KeysIter::new(keys, self.qty.unwrap_or(LIMIT))
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Prefix, Suffix, Value> PaginatedMutation<'a, Key, Value>
+    for PrefixPage<'a, LIMIT, Key, Prefix, Suffix>
+where
+    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+    Prefix: Prefixer<'a> + Serialize,
+    Suffix: PrimaryKey<'a> + KeyDeserialize + Serialize + DeserializeOwned + Clone,
+    Value: Serialize + DeserializeOwned,
+{
+    type MOutput = Suffix::Output;
+
+    fn delete(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> StdResult<MutatedPage<Self::MOutput>> {
+        self.raw_page(map).delete::<Suffix>(storage)
+    }
+
+    fn update<Function>(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+        action: Function,
+    ) -> StdResult<MutatedPage<Self::MOutput>>
+    where
+        Function: FnMut(&Self::MOutput, Value) -> StdResult<Option<Value>>,
+    {
+        self.raw_page(map).update::<Suffix, _, _>(storage, action)
+    }
+
+    fn rekey<NewKey, NewValue, Function>(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+        new_map: &Map<'a, NewKey, NewValue>,
+        delete_old: bool,
+        transform: Function,
+    ) -> StdResult<MutatedPage<Self::MOutput>>
+    where
+        NewKey: PrimaryKey<'a>,
+        NewValue: Serialize + DeserializeOwned,
+        Function: FnMut(&Self::MOutput, Value) -> (NewKey, NewValue),
+    {
+        self.raw_page(map)
+            .rekey::<Suffix, _, _, _, _>(storage, new_map, delete_old, transform)
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Prefix, Suffix> PrefixPage<'a, LIMIT, Key, Prefix, Suffix>
+where
+    Key: PrimaryKey<'a, Prefix = Prefix, Suffix = Suffix>,
+    Prefix: Prefixer<'a> + Serialize,
+    Suffix: PrimaryKey<'a> + KeyDeserialize + Serialize + DeserializeOwned + Clone,
+{
+    fn raw_page<Value>(self, map: &Map<'a, Key, Value>) -> RawPage {
+        RawPage::new(
+            namespace(map),
+            &self.prefix.prefix(),
+            self.start.map(|s| s.joined_key()),
+            self.qty.unwrap_or(LIMIT),
+        )
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{KeysQuery, PaginatedQuery, PrefixPage};
+    use crate::{KeysQuery, Map, PaginatedMutation, PaginatedQuery, PrefixPage, QueryError};
     use cosmwasm_std::testing::mock_dependencies;
-    use cw_storage_plus::Map;
+    use cosmwasm_std::to_json_binary;
 
     #[test]
     fn pagination_iterator() {
@@ -225,4 +346,161 @@ mod test {
         assert_eq!(res.next, Some("string-034".to_string()));
         assert_eq!(res.data.get(0).unwrap(), "string-020");
     }
+
+    #[test]
+    fn borrowed_prefix() {
+        let mut deps = mock_dependencies();
+        let orders: Map<(&str, u32), u8> = Map::new("orders");
+
+        for i in 0..10 {
+            orders
+                .save(deps.as_mut().storage, ("alice", i), &(i as u8))
+                .unwrap();
+            orders
+                .save(deps.as_mut().storage, ("bob", i), &(i as u8))
+                .unwrap();
+        }
+
+        let query: PrefixPage<3, (&str, u32), &str, u32> = PrefixPage {
+            prefix: "bob",
+            start: Some(6),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &orders, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![7, 8, 9]);
+    }
+
+    #[test]
+    fn borrowed_suffix() {
+        let mut deps = mock_dependencies();
+        let names: Map<(u8, &str), u8> = Map::new("names");
+        let raw: Map<(u8, &[u8]), u8> = Map::new("raw");
+
+        for (i, name) in ["alice", "bob", "carol", "dave"].into_iter().enumerate() {
+            names
+                .save(deps.as_mut().storage, (1, name), &(i as u8))
+                .unwrap();
+            raw.save(deps.as_mut().storage, (1, name.as_bytes()), &(i as u8))
+                .unwrap();
+        }
+
+        let query: PrefixPage<2, (u8, &str), u8, &str> = PrefixPage {
+            prefix: 1,
+            start: Some("alice"),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &names, |k, v| (k.clone(), v))
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec![("bob".to_string(), 1), ("carol".to_string(), 2)]
+        );
+        assert_eq!(res.next, Some("carol".to_string()));
+
+        let query: PrefixPage<2, (u8, &[u8]), u8, &[u8]> = PrefixPage {
+            prefix: 1,
+            start: Some(b"bob".as_slice()),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &raw, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![2, 3]);
+        assert_eq!(res.next, Some(b"dave".to_vec()));
+    }
+
+    #[test]
+    fn validate() {
+        let page: PrefixPage<20, (u8, String), u8, String> =
+            PrefixPage::parse(br#"{"prefix":1,"start":"a","qty":20}"#).unwrap();
+        assert_eq!(page.prefix, 1);
+        assert!(page.validate().is_ok());
+
+        assert_eq!(
+            PrefixPage::<20, (u8, String), u8, String>::parse(br#"{"prefix":1,"qty":0}"#),
+            Err(QueryError::ZeroLimit {})
+        );
+        assert!(matches!(
+            PrefixPage::<20, (u8, String), u8, String>::parse(br#"{"prefix":1,"start":2}"#),
+            Err(QueryError::CursorDecode { .. })
+        ));
+        assert!(matches!(
+            PrefixPage::<20, (u8, String), u8, String>::parse(br#"{"prefix":"1"}"#),
+            Err(QueryError::Std(_))
+        ));
+    }
+
+    #[test]
+    fn into_raw_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<(u8, String), u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map
+                .save(deps.as_mut().storage, (1, format!("string-{:0>3}", i)), &i)
+                .unwrap();
+            test_map
+                .save(deps.as_mut().storage, (2, format!("string-{:0>3}", i)), &i)
+                .unwrap();
+        }
+
+        let query: PrefixPage<20, _, _, _> = PrefixPage {
+            prefix: 2,
+            start: Some("string-097".to_string()),
+            qty: None,
+        };
+
+        let res = query
+            .into_raw_pagination(deps.as_ref().storage, &test_map)
+            .unwrap();
+
+        assert_eq!(res.qty, 2);
+        assert_eq!(res.next, Some("string-099".to_string()));
+        assert_eq!(
+            res.data.first().unwrap(),
+            &("string-098".to_string(), to_json_binary(&98u8).unwrap())
+        );
+    }
+
+    #[test]
+    fn paginated_mutation() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<(u8, u8), u8> = Map::new("test_map");
+        let new_map: Map<(u8, u8), u8> = Map::new("new_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, (1, i), &i).unwrap();
+            test_map.save(deps.as_mut().storage, (2, i), &i).unwrap();
+        }
+
+        let page: PrefixPage<20, _, _, _> = PrefixPage {
+            prefix: 2,
+            start: Some(89),
+            qty: None,
+        };
+        let res = page
+            .rekey(deps.as_mut().storage, &test_map, &new_map, true, |k, v| {
+                ((3, *k), v)
+            })
+            .unwrap();
+        assert_eq!(res.qty, 10);
+        assert_eq!(res.next, Some(99));
+        assert!(!test_map.has(deps.as_ref().storage, (2, 99)));
+        assert!(test_map.has(deps.as_ref().storage, (1, 99)));
+        assert_eq!(new_map.load(deps.as_ref().storage, (3, 99)).unwrap(), 99);
+
+        let page: PrefixPage<20, _, _, _> = PrefixPage {
+            prefix: 1,
+            start: None,
+            qty: None,
+        };
+        let res = page.delete(deps.as_mut().storage, &test_map).unwrap();
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(19));
+        assert!(!test_map.has(deps.as_ref().storage, (1, 19)));
+        assert!(test_map.has(deps.as_ref().storage, (2, 19)));
+    }
 }
@@ -0,0 +1,160 @@
+use crate::error::deserialize_key;
+use crate::{namespace, Map, NextPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Empty, Order, Storage};
+use cw_storage_plus::{range_with_prefix, KeyDeserialize, Prefix, PrimaryKey, RawBound};
+
+pub type DefaultPrefixKeysPage<P> = PrefixKeysPage<50, P>;
+
+/// Pages over the distinct first elements of a `(Prefix, Suffix)` keyed map, e.g. every
+/// market that has at least one order. Each prefix costs a single seek no matter how many
+/// entries are stored under it. Prefixes come in storage order, which sorts by length first
+#[cw_serde]
+pub struct PrefixKeysPage<const LIMIT: usize, P> {
+    pub start: Option<P>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, P> PrefixKeysPage<LIMIT, P> {
+    pub fn into_prefix_keys_pagination<'a, S, Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, (P, S), Value>,
+    ) -> Result<NextPage<P::Output, P::Output>, QueryError>
+    where
+        P: PrimaryKey<'a> + KeyDeserialize,
+        P::Output: Clone,
+    {
+        let namespace: Prefix<Vec<u8>, Empty> = Prefix::new(namespace(map), &[]);
+        let limit = self.qty.unwrap_or(LIMIT);
+        let mut seek = match self.start {
+            Some(start) => match after_prefix(&start.joined_key()) {
+                Some(seek) => Some(seek),
+                None => return Ok(empty_page()),
+            },
+            None => None,
+        };
+        let mut data = vec![];
+        let mut end = None;
+
+        while data.len() < limit {
+            let Some((raw_key, _)) = range_with_prefix(
+                storage,
+                &namespace,
+                seek.map(RawBound::Inclusive),
+                None,
+                Order::Ascending,
+            )
+            .next() else {
+                break;
+            };
+
+            let raw_prefix = split_prefix(&raw_key)?;
+            let prefix = deserialize_key::<P>(raw_prefix.to_vec())?;
+            data.push(prefix.clone());
+            end = Some(prefix);
+
+            match after_prefix(raw_prefix) {
+                Some(next) => seek = Some(next),
+                None => break,
+            }
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+fn empty_page<D, K>() -> NextPage<D, K> {
+    NextPage {
+        data: vec![],
+        next: None,
+        qty: 0,
+        scanned: 0,
+        errors: vec![],
+    }
+}
+
+/// First element of a raw composite key, stored behind its big endian `u16` length
+fn split_prefix(raw_key: &[u8]) -> Result<&[u8], QueryError> {
+    let corrupt = || QueryError::KeyDeserialize {
+        raw: raw_key.to_vec().into(),
+    };
+    let len = raw_key.get(..2).ok_or_else(corrupt)?;
+    let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+    raw_key.get(2..2 + len).ok_or_else(corrupt)
+}
+
+/// Smallest raw key sorting after every key stored under `prefix`
+fn after_prefix(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut key = (prefix.len() as u16).to_be_bytes().to_vec();
+    key.extend_from_slice(prefix);
+
+    while let Some(last) = key.pop() {
+        if last < u8::MAX {
+            key.push(last + 1);
+            return Some(key);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, PrefixKeysPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_prefix_keys_pagination() {
+        let mut deps = mock_dependencies();
+        let orders: Map<(String, u64), u64> = Map::new("orders");
+
+        for (i, market) in ["atom", "juno", "osmo", "osmo-usdc", "scrt"]
+            .into_iter()
+            .enumerate()
+        {
+            for id in 0..(i as u64 * 10 + 1) {
+                orders
+                    .save(deps.as_mut().storage, (market.to_string(), id), &id)
+                    .unwrap();
+            }
+        }
+
+        let query: PrefixKeysPage<3, String> = PrefixKeysPage {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_prefix_keys_pagination(deps.as_ref().storage, &orders)
+            .unwrap();
+        assert_eq!(res.data, vec!["atom", "juno", "osmo"]);
+        assert_eq!(res.next, Some("osmo".to_string()));
+
+        let query: PrefixKeysPage<3, String> = PrefixKeysPage {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_prefix_keys_pagination(deps.as_ref().storage, &orders)
+            .unwrap();
+        // Prefixes are stored length prefixed, so shorter ones sort first
+        assert_eq!(res.data, vec!["scrt", "osmo-usdc"]);
+        assert_eq!(res.next, Some("osmo-usdc".to_string()));
+
+        let query: PrefixKeysPage<3, String> = PrefixKeysPage {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_prefix_keys_pagination(deps.as_ref().storage, &orders)
+            .unwrap();
+        assert_eq!(res.qty, 0);
+        assert_eq!(res.next, None);
+    }
+}
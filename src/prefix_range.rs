@@ -0,0 +1,109 @@
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrefixBound, Prefixer, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultPrefixRangePage<K, P> = PrefixRangePage<50, K, P>;
+
+/// Page over the full `(prefix, suffix)` entries of a map starting at the `prefix` bound,
+/// letting a global export jump to a given prefix. Once paging, the `start` cursor takes
+/// precedence over `prefix`
+#[cw_serde]
+pub struct PrefixRangePage<const LIMIT: usize, K, P> {
+    pub prefix: Option<P>,
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key, P> PrefixRangePage<LIMIT, Key, P> {
+    pub fn into_prefix_range_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a, Prefix = P> + KeyDeserialize + 'a,
+        <Key as KeyDeserialize>::Output: 'static,
+        P: Prefixer<'a>,
+        Value: Serialize + DeserializeOwned + 'a,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = match (self.start, self.prefix) {
+            (Some(start), _) => map.range(
+                storage,
+                Some(Bound::Exclusive((start, PhantomData))),
+                None,
+                Order::Ascending,
+            ),
+            (None, prefix) => map.prefix_range(
+                storage,
+                prefix.map(PrefixBound::inclusive),
+                None,
+                Order::Ascending,
+            ),
+        }
+        .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for item in range {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, PrefixRangePage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_prefix_range_pagination() {
+        let mut deps = mock_dependencies();
+        let orders: Map<(u32, u32), u32> = Map::new("orders");
+
+        for market in 0..30 {
+            for id in 0..3 {
+                orders
+                    .save(deps.as_mut().storage, (market, id), &(market * 10 + id))
+                    .unwrap();
+            }
+        }
+
+        let query: PrefixRangePage<4, (u32, u32), u32> = PrefixRangePage {
+            prefix: Some(17),
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_prefix_range_pagination(deps.as_ref().storage, &orders, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![(17, 0), (17, 1), (17, 2), (18, 0)]);
+        assert_eq!(res.next, Some((18, 0)));
+
+        let query: PrefixRangePage<4, (u32, u32), u32> = PrefixRangePage {
+            prefix: Some(17),
+            start: res.next,
+            qty: Some(2),
+        };
+        let res = query
+            .into_prefix_range_pagination(deps.as_ref().storage, &orders, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![181, 182]);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::error::deserialize_key;
+use crate::{namespace, raw_range, Map, NextPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Order, StdError, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+
+pub type DefaultProjectionPage<K> = ProjectionPage<50, K>;
+
+/// Page returning only the requested fields of each value, fields are dot separated
+/// paths (`owner.address`) and missing fields are returned as `null`. Values go through
+/// `serde_json::Value`, whose float support gets a contract rejected by the CosmWasm
+/// validator, so this is meant for off-chain tooling such as indexers and test suites
+#[cw_serde]
+pub struct ProjectionPage<const LIMIT: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    pub fields: Vec<String>,
+}
+
+/// Picks the given paths out of a json value into a flat object keyed by path, off-chain only
+pub fn project(value: &serde_json::Value, fields: &[String]) -> serde_json::Value {
+    let projected = fields
+        .iter()
+        .map(|field| {
+            let found = field
+                .split('.')
+                .try_fold(value, |current, part| current.get(part));
+            (
+                field.clone(),
+                found.cloned().unwrap_or(serde_json::Value::Null),
+            )
+        })
+        .collect();
+
+    serde_json::Value::Object(projected)
+}
+
+impl<const LIMIT: usize, Key> ProjectionPage<LIMIT, Key> {
+    pub fn into_projected_pagination<'a, Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> Result<NextPage<serde_json::Value, Key::Output>, QueryError>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+    {
+        let range = raw_range(
+            storage,
+            namespace(map),
+            &[],
+            self.start.map(|s| s.joined_key()),
+            Order::Ascending,
+        )
+        .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for (raw_key, raw_value) in range {
+            let value: serde_json::Value = serde_json::from_slice(&raw_value)
+                .map_err(|e| StdError::parse_err("serde_json::Value", e))?;
+            data.push(project(&value, &self.fields));
+            end = Some(deserialize_key::<Key>(raw_key)?);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, ProjectionPage};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::mock_dependencies;
+    use serde_json::json;
+
+    #[cw_serde]
+    struct Info {
+        amount: u64,
+        memo: String,
+    }
+
+    #[cw_serde]
+    struct Account {
+        owner: String,
+        info: Info,
+    }
+
+    #[test]
+    fn into_projected_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, Account> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map
+                .save(
+                    deps.as_mut().storage,
+                    i,
+                    &Account {
+                        owner: format!("owner-{}", i),
+                        info: Info {
+                            amount: i as u64,
+                            memo: "memo".to_string(),
+                        },
+                    },
+                )
+                .unwrap();
+        }
+
+        let query: ProjectionPage<20, _> = ProjectionPage {
+            start: Some(9),
+            qty: Some(2),
+            fields: vec![
+                "owner".to_string(),
+                "info.amount".to_string(),
+                "missing".to_string(),
+            ],
+        };
+
+        let res = query
+            .into_projected_pagination(deps.as_ref().storage, &test_map)
+            .unwrap();
+
+        assert_eq!(res.qty, 2);
+        assert_eq!(res.next, Some(11));
+        assert_eq!(
+            res.data.first().unwrap(),
+            &json!({"owner": "owner-10", "info.amount": 10, "missing": null})
+        );
+    }
+}
@@ -1,15 +1,29 @@
-use crate::{KeysQuery, NextPage, PaginatedQuery};
+use crate::error::{deserialize_key, parse_page, validate_qty};
+use crate::{
+    namespace, raw_range, ErrorPolicy, KeysIter, KeysQuery, Map, MutatedPage, NextPage, PageError,
+    PaginatedMutation, PaginatedQuery, QueryError, RawNextPage, RawPage, MAX_QUERY_RESPONSE_BYTES,
+};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::serde::de::DeserializeOwned;
 use cosmwasm_schema::serde::Serialize;
-use cosmwasm_std::{Order, StdResult, Storage};
-use cw_storage_plus::{Bound, KeyDeserialize, Map, PrimaryKey};
-use std::iter::Take;
+use cosmwasm_std::{from_json, to_json_vec, Binary, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
 use std::marker::PhantomData;
 
 pub type DefaultPage<'a, S> = Page<50, S>;
 
+/// Unknown fields are rejected, so a misspelled `start` fails instead of silently paging
+/// from the beginning.
+///
+/// Out of range `qty` values are handled in one of two ways. [`Page::validate`] and
+/// [`Page::resolve`] reject a zero or oversized `qty` with a [`QueryError`].
+/// [`Page::all_up_to`] caps it at `LIMIT` and [`Page::clamp`] clamps it between one and
+/// the max instead, for endpoints that prefer serving a smaller page over failing the query
 #[cw_serde]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
 pub struct Page<const LIMIT: usize, K> {
     pub start: Option<K>,
     pub qty: Option<usize>,
@@ -20,7 +34,7 @@ impl<'a, const LIMIT: usize, Key, Value, Data> PaginatedQuery<'a, Key, Value, Da
 where
     Key: PrimaryKey<'a> + KeyDeserialize + Clone,
     <Key as KeyDeserialize>::Output: 'static,
-    Value: Serialize + DeserializeOwned + Clone,
+    Value: Serialize + DeserializeOwned,
 {
     type POutput = NextPage<Data, Key::Output>;
     type FuncKey = Key::Output;
@@ -28,36 +42,395 @@ where
     fn into_pagination<Function>(
         self,
         storage: &'a dyn Storage,
-        map: &Map<'static, Key, Value>,
+        map: &Map<'a, Key, Value>,
         transform: Function,
     ) -> StdResult<Self::POutput>
     where
         Function: FnOnce(&Self::FuncKey, Value) -> Data + Copy,
     {
-        let mut range = map
+        let limit = self.qty.unwrap_or(LIMIT);
+        let range = map
             .range(
                 storage,
                 self.start.map(|s| Bound::Exclusive((s, PhantomData))),
                 None,
                 Order::Ascending,
             )
-            .take(self.qty.unwrap_or(LIMIT));
-        let mut data = vec![];
+            .take(limit);
+        // `qty` is client provided, capping at LIMIT avoids preallocating for oversized requests
+        let mut data = Vec::with_capacity(limit.min(LIMIT));
         let mut end = None;
 
-        let mut next = range.next();
+        for item in range {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<const LIMIT: usize, Key> Default for Page<LIMIT, Key> {
+    fn default() -> Self {
+        Self::first()
+    }
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Up to `LIMIT` entries from the beginning of the map
+    pub fn first() -> Self {
+        Page {
+            start: None,
+            qty: None,
+        }
+    }
+
+    /// Up to `LIMIT` entries after `key`, usually the `next` cursor of the previous page
+    pub fn after(key: Key) -> Self {
+        Page {
+            start: Some(key),
+            qty: None,
+        }
+    }
 
-        while let Some(key) = next {
-            let (key, value) = key?;
+    /// Up to `qty` entries from the beginning of the map, capped at `LIMIT`
+    pub fn all_up_to(qty: usize) -> Self {
+        Page {
+            start: None,
+            qty: Some(qty.min(LIMIT)),
+        }
+    }
 
-            next = range.next();
+    /// Builds a page, rejecting a `qty` of zero or above `LIMIT`
+    pub fn new(start: Option<Key>, qty: Option<usize>) -> Result<Self, QueryError> {
+        let page = Page { start, qty };
+        page.validate()?;
+        Ok(page)
+    }
 
+    /// Checks a client provided page, pagination itself doesn't enforce `LIMIT` on `qty`
+    pub fn validate(&self) -> Result<(), QueryError> {
+        validate_qty(self.qty, LIMIT)
+    }
+
+    /// Deserializes and validates a json encoded page
+    pub fn parse(value: impl AsRef<[u8]>) -> Result<Self, QueryError>
+    where
+        Key: DeserializeOwned,
+    {
+        let page: Self = parse_page::<_, Key>(value.as_ref())?;
+        page.validate()?;
+        Ok(page)
+    }
+
+    /// Same as `into_pagination` but cuts the page before the serialized response grows past
+    /// `max_response_bytes`, defaulting to [`MAX_QUERY_RESPONSE_BYTES`]. Errors when the first
+    /// item alone doesn't fit, since the cursor could never move past it
+    pub fn into_sized_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        max_response_bytes: Option<usize>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: Serialize + 'static,
+        Value: Serialize + DeserializeOwned,
+        Data: Serialize,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let max_response_bytes = max_response_bytes.unwrap_or(MAX_QUERY_RESPONSE_BYTES);
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+        let mut size = 0;
+
+        for item in range {
+            let (key, value) = item?;
             let res = transform(&key, value);
-            if next.is_none() {
-                end = Some(key);
+
+            // Account for the separating comma and for the page fields around the data,
+            // which grow with the cursor and the counters
+            let item_size = to_json_vec(&res)?.len() + usize::from(!data.is_empty());
+            let qty = data.len() + 1;
+            let envelope: NextPage<Data, &Key::Output> = NextPage {
+                data: vec![],
+                next: Some(&key),
+                qty,
+                scanned: qty,
+                errors: vec![],
+            };
+            if size + item_size + to_json_vec(&envelope)?.len() > max_response_bytes {
+                if data.is_empty() {
+                    return Err(StdError::generic_err(
+                        "Page item is larger than the response size limit",
+                    ));
+                }
+                break;
             }
 
+            size += item_size;
             data.push(res);
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+
+    /// Stops once the summed `weight` of the returned items would exceed `budget`, the first
+    /// item is always returned so a single item heavier than the budget can't stall the cursor
+    pub fn into_weighted_pagination<'a, Value, Data, Weight, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        budget: u64,
+        weight: Weight,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Weight: Fn(&Key::Output, &Value) -> u64,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+        let mut total: u64 = 0;
+
+        for item in range {
+            let (key, value) = item?;
+
+            total = total.saturating_add(weight(&key, &value));
+            if total > budget && !data.is_empty() {
+                break;
+            }
+
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+
+    /// Same as `into_pagination` but entries the transform maps to `None` are left out.
+    /// They still use up the requested page size, advance the cursor and are counted in
+    /// `scanned`, while the returned `qty` only counts the items kept
+    pub fn into_filter_map_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Option<Data> + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            let (key, value) = item?;
+            scanned += 1;
+            data.extend(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+
+    /// Same as `into_pagination` but each entry expands into any number of items. The
+    /// requested `qty` limits the source entries so the cursor always lands on a whole entry,
+    /// the returned `qty` counts the expanded items and `scanned` the source entries read
+    pub fn into_flat_map_pagination<'a, Value, Data, Items, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Items: IntoIterator<Item = Data>,
+        Function: FnOnce(&Key::Output, Value) -> Items + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            let (key, value) = item?;
+            scanned += 1;
+            data.extend(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+
+    /// Paginates without deserializing the values, returning the stored bytes as is
+    pub fn into_raw_pagination<'a, Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> Result<RawNextPage<Key::Output>, QueryError>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: Clone,
+    {
+        let range = raw_range(
+            storage,
+            namespace(map),
+            &[],
+            self.start.map(|s| s.joined_key()),
+            Order::Ascending,
+        )
+        .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for (raw_key, raw_value) in range {
+            let key = deserialize_key::<Key>(raw_key)?;
+            data.push((key.clone(), raw_value.into()));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<const LIMIT: usize> Page<LIMIT, Binary> {
+    /// Same as `into_pagination` but entries failing to deserialize are handled according
+    /// to `policy`. Skipped entries are reported in `errors`, use up the requested `qty` and
+    /// count towards `scanned`, so a run of corrupt entries can't make the page read past
+    /// its limit. The cursor is the raw key of the last entry read, so pages move past keys
+    /// that can't be decoded
+    pub fn into_policy_pagination<'a, Key, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        policy: ErrorPolicy,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Binary>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        Value: DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = raw_range(
+            storage,
+            namespace(map),
+            &[],
+            self.start.map(|s| s.to_vec()),
+            Order::Ascending,
+        )
+        .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut errors = vec![];
+        let mut end = None;
+
+        for (raw_key, raw_value) in range {
+            end = Some(Binary::from(raw_key.as_slice()));
+
+            let key = match deserialize_key::<Key>(raw_key.clone()) {
+                Ok(key) => key,
+                Err(e) if policy == ErrorPolicy::Skip => {
+                    errors.push(PageError {
+                        key: raw_key.into(),
+                        error: e.to_string(),
+                    });
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            match from_json::<Value>(&raw_value) {
+                Ok(value) => data.push(transform(&key, value)),
+                Err(e) if policy == ErrorPolicy::Skip => errors.push(PageError {
+                    key: raw_key.into(),
+                    error: e.to_string(),
+                }),
+                Err(e) => return Err(e),
+            }
         }
 
         let len = data.len();
@@ -65,36 +438,102 @@ where
             data,
             next: end,
             qty: len,
+            scanned: len + errors.len(),
+            errors,
         })
     }
 }
+
 impl<'a, const LIMIT: usize, Key, Value> KeysQuery<'a, Key, Value> for Page<LIMIT, Key>
 where
     Key: PrimaryKey<'a> + KeyDeserialize + Clone,
     <Key as KeyDeserialize>::Output: 'static,
-    Value: Serialize + DeserializeOwned + Clone + 'static,
+    Value: Serialize + DeserializeOwned + 'a,
 {
     type KOutput = Key::Output;
     fn keys(
         self,
         storage: &'a dyn Storage,
-        map: &Map<'static, Key, Value>,
-    ) -> Take<Box<dyn Iterator<Item = StdResult<Self::KOutput>> + 'a>> {
-        map.keys(
+        map: &Map<'a, Key, Value>,
+    ) -> KeysIter<'a, Self::KOutput> {
+        let keys = map.keys(
             storage,
             self.start.map(|s| Bound::Exclusive((s, PhantomData))),
             None,
             Order::Ascending,
+        );
+        KeysIter::new(keys, self.qty.unwrap_or(LIMIT))
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Value> PaginatedMutation<'a, Key, Value> for Page<LIMIT, Key>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    Value: Serialize + DeserializeOwned,
+{
+    type MOutput = Key::Output;
+
+    fn delete(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> StdResult<MutatedPage<Self::MOutput>> {
+        self.raw_page(map).delete::<Key>(storage)
+    }
+
+    fn update<Function>(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+        action: Function,
+    ) -> StdResult<MutatedPage<Self::MOutput>>
+    where
+        Function: FnMut(&Self::MOutput, Value) -> StdResult<Option<Value>>,
+    {
+        self.raw_page(map).update::<Key, _, _>(storage, action)
+    }
+
+    fn rekey<NewKey, NewValue, Function>(
+        self,
+        storage: &mut dyn Storage,
+        map: &Map<'a, Key, Value>,
+        new_map: &Map<'a, NewKey, NewValue>,
+        delete_old: bool,
+        transform: Function,
+    ) -> StdResult<MutatedPage<Self::MOutput>>
+    where
+        NewKey: PrimaryKey<'a>,
+        NewValue: Serialize + DeserializeOwned,
+        Function: FnMut(&Self::MOutput, Value) -> (NewKey, NewValue),
+    {
+        self.raw_page(map)
+            .rekey::<Key, _, _, _, _>(storage, new_map, delete_old, transform)
+    }
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    fn raw_page<'a, Value>(self, map: &Map<'a, Key, Value>) -> RawPage
+    where
+        Key: PrimaryKey<'a>,
+    {
+        RawPage::new(
+            namespace(map),
+            &[],
+            self.start.map(|s| s.joined_key()),
+            self.qty.unwrap_or(LIMIT),
         )
-        .take(self.qty.unwrap_or(LIMIT))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::{KeysQuery, Page, PaginatedQuery};
+    use crate::{
+        dynamic_map, ErrorPolicy, KeysQuery, Map, Page, PaginatedMutation, PaginatedQuery,
+        QueryError,
+    };
+    use cosmwasm_schema::serde::{Deserialize, Serialize};
     use cosmwasm_std::testing::mock_dependencies;
-    use cw_storage_plus::Map;
+    use cosmwasm_std::{from_json, to_json_binary, Binary, Storage};
 
     #[test]
     fn pagination_iterator() {
@@ -187,8 +626,144 @@ mod test {
             start: None,
             qty: None,
         };
+        let keys = query
+            .keys(deps.as_ref().storage, &test_map)
+            .collect_page()
+            .unwrap();
+        assert_eq!(keys.len(), 20);
+        assert_eq!(keys.next, Some(vec![19]));
 
-        let _ = query.keys(deps.as_ref().storage, &test_map);
+        let query: Page<20, &[u8]> = Page {
+            start: Some(&[97]),
+            qty: Some(2),
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec!["string-98", "string-99"]);
+        assert_eq!(res.next, Some(vec![99]));
+    }
+
+    #[test]
+    fn borrowed_keys() {
+        let mut deps = mock_dependencies();
+        let names: Map<&str, u8> = Map::new("names");
+        let raw: Map<&[u8], u8> = Map::new("raw");
+
+        for (i, name) in ["alice", "bob", "carol", "dave"].into_iter().enumerate() {
+            names.save(deps.as_mut().storage, name, &(i as u8)).unwrap();
+            raw.save(deps.as_mut().storage, name.as_bytes(), &(i as u8))
+                .unwrap();
+        }
+
+        let query: Page<2, &str> = Page {
+            start: Some("alice"),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &names, |k, v| (k.clone(), v))
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec![("bob".to_string(), 1), ("carol".to_string(), 2)]
+        );
+
+        let query: Page<2, &[u8]> = Page {
+            start: Some(b"bob".as_slice()),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &raw, |k, _| k.clone())
+            .unwrap();
+        assert_eq!(res.data, vec![b"carol".to_vec(), b"dave".to_vec()]);
+    }
+
+    #[test]
+    fn scoped_map() {
+        let mut deps = mock_dependencies();
+        let owner = "alice".to_string();
+        let namespace = format!("orders:{}", owner);
+        let orders: Map<u8, u8> = dynamic_map(&namespace);
+
+        for i in 0..10 {
+            orders.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let query: Page<3, u8> = Page {
+            start: Some(2),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &orders, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![3, 4, 5]);
+
+        let query: Page<3, u8> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let keys: Vec<u8> = query
+            .keys(deps.as_ref().storage, &orders)
+            .map(|k| k.unwrap())
+            .collect();
+        assert_eq!(keys, vec![6, 7, 8]);
+    }
+
+    #[test]
+    fn signed_keys() {
+        // cw-storage-plus flips the sign bit of signed int keys, so they page in numeric order
+        let mut deps = mock_dependencies();
+        let balances: Map<i64, u8> = Map::new("balances");
+
+        for i in [-300i64, -2, -1, 0, 1, 250, i64::MIN, i64::MAX] {
+            balances.save(deps.as_mut().storage, i, &0).unwrap();
+        }
+
+        let query: Page<4, i64> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &balances, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![i64::MIN, -300, -2, -1]);
+
+        let query: Page<4, i64> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &balances, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![0, 1, 250, i64::MAX]);
+    }
+
+    #[test]
+    fn into_pagination_without_clone() {
+        // Values only need to be (de)serializable
+        #[derive(Serialize, Deserialize)]
+        #[serde(crate = "cosmwasm_schema::serde")]
+        struct Order {
+            amount: u64,
+        }
+
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, Order> = Map::new("test_map");
+
+        for i in 0..10 {
+            test_map
+                .save(deps.as_mut().storage, i, &Order { amount: i as u64 })
+                .unwrap();
+        }
+
+        let query: Page<3, _> = Page {
+            start: Some(4),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &test_map, |_, v| v.amount)
+            .unwrap();
+        assert_eq!(res.data, vec![5, 6, 7]);
     }
 
     #[test]
@@ -266,6 +841,320 @@ mod test {
         assert_eq!(res.data.get(19).unwrap(), "new-string-019");
     }
 
+    #[test]
+    fn validate() {
+        assert!(Page::<20, u8>::new(Some(1), Some(20)).is_ok());
+        assert!(Page::<20, u8>::new(None, None).is_ok());
+        assert_eq!(
+            Page::<20, u8>::new(None, Some(0)),
+            Err(QueryError::ZeroLimit {})
+        );
+        assert_eq!(
+            Page::<20, u8>::new(None, Some(21)),
+            Err(QueryError::LimitExceeded {
+                requested: 21,
+                max: 20
+            })
+        );
+
+        let page = Page::<20, u8>::parse(br#"{"start":4,"qty":5}"#).unwrap();
+        assert_eq!(page.start, Some(4));
+        assert!(matches!(
+            Page::<20, u8>::parse(br#"{"start":"four","qty":5}"#),
+            Err(QueryError::CursorDecode { .. })
+        ));
+        assert!(matches!(
+            Page::<20, u8>::parse(br#"{"start":4,"qty":50}"#),
+            Err(QueryError::LimitExceeded { .. })
+        ));
+        // Mistakes outside of the cursor aren't reported as cursor errors
+        assert!(matches!(
+            Page::<20, u8>::parse(br#"{"strt":4,"qty":5}"#),
+            Err(QueryError::Std(_))
+        ));
+        assert!(matches!(
+            Page::<20, u8>::parse(br#"{"start":4,"qty":"five"}"#),
+            Err(QueryError::Std(_))
+        ));
+        assert!(from_json::<Page<20, u8>>(br#"{"start":4,"limit":5}"#).is_err());
+    }
+
+    #[test]
+    fn into_raw_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<String, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map
+                .save(deps.as_mut().storage, format!("string-{:0>3}", i), &i)
+                .unwrap();
+        }
+
+        let query: Page<20, _> = Page {
+            start: Some("string-009".to_string()),
+            qty: Some(5),
+        };
+
+        let res = query
+            .into_raw_pagination(deps.as_ref().storage, &test_map)
+            .unwrap();
+
+        assert_eq!(res.qty, 5);
+        assert_eq!(res.next, Some("string-014".to_string()));
+        assert_eq!(
+            res.data.first().unwrap(),
+            &("string-010".to_string(), to_json_binary(&10u8).unwrap())
+        );
+    }
+
+    #[test]
+    fn paginated_mutation() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let page: Page<20, _> = Page {
+            start: None,
+            qty: Some(10),
+        };
+        let res = page.delete(deps.as_mut().storage, &test_map).unwrap();
+        assert_eq!(res.qty, 10);
+        assert_eq!(res.next, Some(9));
+        assert!(!test_map.has(deps.as_ref().storage, 9));
+
+        let page: Page<20, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = page
+            .update(deps.as_mut().storage, &test_map, |k, v| {
+                Ok((k % 2 == 0).then_some(v * 2))
+            })
+            .unwrap();
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(29));
+        assert_eq!(test_map.load(deps.as_ref().storage, 10).unwrap(), 20);
+        assert!(!test_map.has(deps.as_ref().storage, 11));
+        assert_eq!(test_map.load(deps.as_ref().storage, 30).unwrap(), 30);
+    }
+
+    #[test]
+    fn into_sized_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, String> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map
+                .save(deps.as_mut().storage, i, &"a".repeat(98))
+                .unwrap();
+        }
+
+        let query: Page<50, _> = Page {
+            start: None,
+            qty: None,
+        };
+
+        // Every item serializes into 100 bytes plus a separator, ten of them and the page
+        // fields add up to 1051 bytes
+        let res = query
+            .into_sized_pagination(deps.as_ref().storage, &test_map, Some(1100), |_, v| v)
+            .unwrap();
+
+        assert_eq!(res.qty, 10);
+        assert_eq!(res.next, Some(9));
+
+        let query: Page<50, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+
+        let res = query
+            .into_sized_pagination(deps.as_ref().storage, &test_map, None, |_, v| v)
+            .unwrap();
+
+        assert_eq!(res.qty, 50);
+        assert_eq!(res.next, Some(59));
+
+        // A single item above the limit can't be returned at all
+        let query: Page<50, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+        assert!(query
+            .into_sized_pagination(deps.as_ref().storage, &test_map, Some(100), |_, v| v)
+            .is_err());
+    }
+
+    #[test]
+    fn into_filter_map_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let query: Page<10, _> = Page {
+            start: Some(4),
+            qty: None,
+        };
+        let res = query
+            .into_filter_map_pagination(deps.as_ref().storage, &test_map, |k, v| {
+                (v % 3 == 0).then_some(*k)
+            })
+            .unwrap();
+
+        assert_eq!(res.data, vec![6, 9, 12]);
+        assert_eq!(res.qty, 3);
+        assert_eq!(res.scanned, 10);
+        assert_eq!(res.next, Some(14));
+    }
+
+    #[test]
+    fn into_flat_map_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, Vec<String>> = Map::new("test_map");
+
+        for i in 0..20 {
+            let positions = (0..i % 3).map(|p| format!("{}-{}", i, p)).collect();
+            test_map.save(deps.as_mut().storage, i, &positions).unwrap();
+        }
+
+        let query: Page<10, _> = Page {
+            start: None,
+            qty: Some(4),
+        };
+        let res = query
+            .into_flat_map_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+
+        // Entry 0 expands to nothing and entry 2 to two items
+        assert_eq!(res.data, vec!["1-0", "2-0", "2-1"]);
+        assert_eq!(res.qty, 3);
+        assert_eq!(res.scanned, 4);
+        assert_eq!(res.next, Some(3));
+    }
+
+    #[test]
+    fn into_policy_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+
+        for i in 0..10 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+        // Value that isn't json and keys too short to be a u32
+        let full_key = |key: &[u8]| [b"\x00\x08test_map".as_slice(), key].concat();
+        deps.storage.set(&full_key(&3u32.to_be_bytes()), b"corrupt");
+        deps.storage.set(&full_key(&[0]), b"5");
+        deps.storage.set(&full_key(&[0, 0, 0]), b"5");
+
+        let query: Page<20, Binary> = Page {
+            start: None,
+            qty: Some(2),
+        };
+        assert!(query
+            .clone()
+            .into_policy_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                ErrorPolicy::Abort,
+                |k: &u32, _| *k
+            )
+            .is_err());
+
+        // Every entry of the first page is corrupt, the cursor still moves past them
+        let res = query
+            .into_policy_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                ErrorPolicy::Skip,
+                |k: &u32, _| *k,
+            )
+            .unwrap();
+        assert!(res.data.is_empty());
+        assert_eq!(res.next, Some(Binary::from(vec![0, 0, 0])));
+        assert_eq!(res.scanned, 2);
+
+        let query: Page<20, Binary> = Page {
+            start: res.next,
+            qty: Some(5),
+        };
+        let res = query
+            .into_policy_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                ErrorPolicy::Skip,
+                |k: &u32, _| *k,
+            )
+            .unwrap();
+        assert_eq!(res.data, vec![0, 1, 2, 4]);
+        assert_eq!(res.next, Some(Binary::from(4u32.to_be_bytes().to_vec())));
+        assert_eq!(res.errors.len(), 1);
+        assert_eq!(res.errors[0].key, Binary::from(3u32.to_be_bytes().to_vec()));
+
+        let query: Page<20, Binary> = Page {
+            start: res.next,
+            qty: Some(5),
+        };
+        let res = query
+            .into_policy_pagination(
+                deps.as_ref().storage,
+                &test_map,
+                ErrorPolicy::Skip,
+                |k: &u32, _| *k,
+            )
+            .unwrap();
+        assert_eq!(res.data, vec![5, 6, 7, 8, 9]);
+        assert!(res.errors.is_empty());
+    }
+
+    #[test]
+    fn into_weighted_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u64> = Map::new("test_map");
+
+        for i in 0..100 {
+            let weight = if i % 10 == 0 { 100 } else { 1 };
+            test_map.save(deps.as_mut().storage, i, &weight).unwrap();
+        }
+
+        let query: Page<50, _> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_weighted_pagination(deps.as_ref().storage, &test_map, 120, |_, v| *v, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, (0..10).collect::<Vec<u8>>());
+        assert_eq!(res.next, Some(9));
+
+        let query: Page<50, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_weighted_pagination(deps.as_ref().storage, &test_map, 50, |_, v| *v, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, vec![10]);
+        assert_eq!(res.next, Some(10));
+
+        let query: Page<5, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_weighted_pagination(deps.as_ref().storage, &test_map, 50, |_, v| *v, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, vec![11, 12, 13, 14, 15]);
+    }
+
     const TEST_MAP: Map<'static, &str, u8> = Map::new("TEST_MAP");
     #[test]
     fn into_pagination_ref_static_map() {
@@ -296,4 +1185,26 @@ mod test {
         assert_eq!(res.data.get(0).unwrap(), "new-string-000");
         assert_eq!(res.data.get(19).unwrap(), "new-string-019");
     }
+
+    #[test]
+    fn constructors() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..10 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let page = |query: Page<4, u8>| {
+            query
+                .into_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+                .unwrap()
+                .data
+        };
+        assert_eq!(page(Page::first()), vec![0, 1, 2, 3]);
+        assert_eq!(page(Page::default()), vec![0, 1, 2, 3]);
+        assert_eq!(page(Page::after(7)), vec![8, 9]);
+        assert_eq!(page(Page::all_up_to(2)), vec![0, 1]);
+        assert_eq!(Page::<4, u8>::all_up_to(10).qty, Some(4));
+    }
 }
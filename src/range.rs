@@ -0,0 +1,182 @@
+use crate::{KeysIter, KeysQuery, Map, NextPage, PageOrder, PaginatedQuery};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultRangePage<K> = RangePage<50, K>;
+
+/// Page over a bounded range in either direction, `start` is the cursor and `end` the
+/// exclusive stop key. When descending `start` is the upper bound and `end` the lower one.
+/// `inclusive` returns the `start` key itself, only meant for the first page
+#[cw_serde]
+pub struct RangePage<const LIMIT: usize, K> {
+    pub start: Option<K>,
+    pub end: Option<K>,
+    #[serde(default)]
+    pub inclusive: bool,
+    #[serde(default)]
+    pub order: PageOrder,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key> RangePage<LIMIT, Key> {
+    fn bounds<'a>(self) -> (Option<Bound<'a, Key>>, Option<Bound<'a, Key>>, Order)
+    where
+        Key: PrimaryKey<'a>,
+    {
+        let inclusive = self.inclusive;
+        let start = self.start.map(|s| {
+            if inclusive {
+                Bound::Inclusive((s, PhantomData))
+            } else {
+                Bound::Exclusive((s, PhantomData))
+            }
+        });
+        let end = self.end.map(|e| Bound::Exclusive((e, PhantomData)));
+
+        match self.order {
+            PageOrder::Ascending => (start, end, Order::Ascending),
+            PageOrder::Descending => (end, start, Order::Descending),
+        }
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Value, Data> PaginatedQuery<'a, Key, Value, Data>
+    for RangePage<LIMIT, Key>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    <Key as KeyDeserialize>::Output: 'static,
+    Value: Serialize + DeserializeOwned,
+{
+    type POutput = NextPage<Data, Key::Output>;
+    type FuncKey = Key::Output;
+
+    fn into_pagination<Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<Self::POutput>
+    where
+        Function: FnOnce(&Self::FuncKey, Value) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let (min, max, order) = self.bounds();
+        let range = map.range(storage, min, max, order).take(limit);
+        let mut data = Vec::with_capacity(limit.min(LIMIT));
+        let mut end = None;
+
+        for item in range {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+impl<'a, const LIMIT: usize, Key, Value> KeysQuery<'a, Key, Value> for RangePage<LIMIT, Key>
+where
+    Key: PrimaryKey<'a> + KeyDeserialize,
+    <Key as KeyDeserialize>::Output: 'static,
+    Value: Serialize + DeserializeOwned + 'a,
+{
+    type KOutput = Key::Output;
+    fn keys(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+    ) -> KeysIter<'a, Self::KOutput> {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let (min, max, order) = self.bounds();
+        KeysIter::new(map.keys(storage, min, max, order), limit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{KeysQuery, Map, PageOrder, PaginatedQuery, RangePage};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{from_json, StdResult};
+
+    #[test]
+    fn keys_match_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..20 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let page = |start, end, inclusive, order| -> RangePage<4, u8> {
+            RangePage {
+                start,
+                end,
+                inclusive,
+                order,
+                qty: None,
+            }
+        };
+        let cases = [
+            (
+                page(Some(5), None, false, PageOrder::Ascending),
+                vec![6, 7, 8, 9],
+            ),
+            (
+                page(Some(5), None, true, PageOrder::Ascending),
+                vec![5, 6, 7, 8],
+            ),
+            (
+                page(Some(5), Some(8), false, PageOrder::Ascending),
+                vec![6, 7],
+            ),
+            (
+                page(None, None, false, PageOrder::Descending),
+                vec![19, 18, 17, 16],
+            ),
+            (
+                page(Some(16), Some(13), false, PageOrder::Descending),
+                vec![15, 14],
+            ),
+            (
+                page(Some(16), Some(13), true, PageOrder::Descending),
+                vec![16, 15, 14],
+            ),
+        ];
+
+        for (query, expected) in cases {
+            let keys: Vec<u8> = query
+                .clone()
+                .keys(deps.as_ref().storage, &test_map)
+                .collect::<StdResult<_>>()
+                .unwrap();
+            assert_eq!(keys, expected);
+
+            let res = query
+                .into_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+                .unwrap();
+            assert_eq!(res.data, expected);
+            assert_eq!(res.next, expected.last().copied());
+        }
+
+        // Order and inclusive default when left out of the message
+        let query: RangePage<4, u8> = from_json(br#"{"start":2,"end":null,"qty":2}"#).unwrap();
+        assert_eq!(query.order, PageOrder::Ascending);
+        assert!(!query.inclusive);
+        assert!(from_json::<RangePage<4, u8>>(
+            br#"{"start":2,"end":null,"ordr":"descending","qty":2}"#
+        )
+        .is_err());
+    }
+}
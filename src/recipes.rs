@@ -0,0 +1,737 @@
+use crate::compat::namespace;
+use crate::{Map, MultiIndex, NextPage, Page};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Addr, CustomQuery, Deps, Empty, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Key, Prefix, PrimaryKey};
+use std::marker::PhantomData;
+
+/// Entry returned by [`balances`]
+#[cw_serde]
+pub struct Balance {
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+/// Entry returned by [`approvals`]
+#[cw_serde]
+pub struct Approval<V> {
+    pub spender: Addr,
+    pub info: V,
+}
+
+/// Entry returned by [`allowances`] and [`all_allowances`]
+#[cw_serde]
+pub struct Allowance<V> {
+    pub owner: Addr,
+    pub spender: Addr,
+    pub info: V,
+}
+
+/// cw20 style balance listing over a `Map<&Addr, Uint128>`
+pub fn balances<C, const LIMIT: usize>(
+    deps: Deps<C>,
+    map: &Map<'static, &Addr, Uint128>,
+    page: Page<LIMIT, Addr>,
+) -> StdResult<NextPage<Balance, Addr>>
+where
+    C: CustomQuery,
+{
+    let range = map.range(
+        deps.storage,
+        page.start
+            .map(|s| Bound::ExclusiveRaw(s.as_bytes().to_vec())),
+        None,
+        Order::Ascending,
+    );
+
+    collect(range, page.qty.unwrap_or(LIMIT), |address, amount| {
+        Balance { address, amount }
+    })
+}
+
+/// cw721 style token listing over an owner `MultiIndex` whose primary key is the token id
+pub fn tokens_by_owner<'a, C, T, const LIMIT: usize>(
+    deps: Deps<'a, C>,
+    index: &MultiIndex<'a, Addr, T, String>,
+    owner: Addr,
+    page: Page<LIMIT, String>,
+) -> StdResult<NextPage<String, String>>
+where
+    C: CustomQuery,
+    T: Serialize + DeserializeOwned + Clone,
+{
+    let range = index
+        .prefix(owner)
+        .keys(
+            deps.storage,
+            page.start.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .map(|item| item.map(|k| (k, ())));
+
+    collect(range, page.qty.unwrap_or(LIMIT), |token_id, _| token_id)
+}
+
+/// Approvals granted on a single token, over a `Map<(&str, &Addr), V>`
+/// keyed by token id and spender
+pub fn approvals<C, V, const LIMIT: usize>(
+    deps: Deps<C>,
+    map: &Map<'static, (&str, &Addr), V>,
+    token_id: &str,
+    page: Page<LIMIT, Addr>,
+) -> StdResult<NextPage<Approval<V>, Addr>>
+where
+    C: CustomQuery,
+    V: Serialize + DeserializeOwned,
+{
+    let prefix: Prefix<&Addr, V, &Addr> =
+        Prefix::new(namespace(map), &[Key::Ref(token_id.as_bytes())]);
+    let range = prefix.range(
+        deps.storage,
+        page.start
+            .map(|s| Bound::ExclusiveRaw(s.as_bytes().to_vec())),
+        None,
+        Order::Ascending,
+    );
+
+    collect(range, page.qty.unwrap_or(LIMIT), |spender, info| Approval {
+        spender,
+        info,
+    })
+}
+
+/// cw20 style allowances granted by `owner`, over a `Map<(&Addr, &Addr), V>` keyed by
+/// owner and spender. Takes the owner and cursor strings of the query message as is and
+/// validates them
+pub fn allowances<C, V, const LIMIT: usize>(
+    deps: Deps<C>,
+    map: &Map<'static, (&Addr, &Addr), V>,
+    owner: &str,
+    page: Page<LIMIT, String>,
+) -> StdResult<NextPage<Allowance<V>, String>>
+where
+    C: CustomQuery,
+    V: Serialize + DeserializeOwned,
+{
+    let owner = deps.api.addr_validate(owner)?;
+    let start = page
+        .start
+        .map(|spender| deps.api.addr_validate(&spender))
+        .transpose()?;
+    let prefix: Prefix<&Addr, V, &Addr> =
+        Prefix::new(namespace(map), &[Key::Ref(owner.as_bytes())]);
+    let range = prefix.range(
+        deps.storage,
+        start.map(|s| Bound::ExclusiveRaw(s.as_bytes().to_vec())),
+        None,
+        Order::Ascending,
+    );
+
+    let res = collect(range, page.qty.unwrap_or(LIMIT), |spender, info| {
+        Allowance {
+            owner: owner.clone(),
+            spender,
+            info,
+        }
+    })?;
+    Ok(map_cursor(res, Addr::into_string))
+}
+
+/// Every owner and spender pair of a `Map<(&Addr, &Addr), V>`, the cursor strings are
+/// validated like in [`allowances`]
+pub fn all_allowances<C, V, const LIMIT: usize>(
+    deps: Deps<C>,
+    map: &Map<'static, (&Addr, &Addr), V>,
+    page: Page<LIMIT, (String, String)>,
+) -> StdResult<NextPage<Allowance<V>, (String, String)>>
+where
+    C: CustomQuery,
+    V: Serialize + DeserializeOwned,
+{
+    let start = page
+        .start
+        .map(|(owner, spender)| -> StdResult<_> {
+            Ok((
+                deps.api.addr_validate(&owner)?,
+                deps.api.addr_validate(&spender)?,
+            ))
+        })
+        .transpose()?;
+    let range = map.range(
+        deps.storage,
+        start.map(|(owner, spender)| Bound::ExclusiveRaw((&owner, &spender).joined_key())),
+        None,
+        Order::Ascending,
+    );
+
+    let res = collect(
+        range,
+        page.qty.unwrap_or(LIMIT),
+        |(owner, spender), info| Allowance {
+            owner,
+            spender,
+            info,
+        },
+    )?;
+    Ok(map_cursor(res, |(owner, spender)| {
+        (owner.into_string(), spender.into_string())
+    }))
+}
+
+/// Entry returned by [`Leaderboard::page`]
+#[cw_serde]
+pub struct Ranking {
+    pub address: Addr,
+    pub score: Uint128,
+}
+
+/// Mismatch between the scores and the rank index of a [`Leaderboard`]
+#[cw_serde]
+pub enum IndexIssue {
+    /// Score without its index entry
+    MissingIndex { address: Addr, score: Uint128 },
+    /// Index entry whose score is gone or differs, `stored` is the current score
+    StaleIndex {
+        address: Addr,
+        score: Uint128,
+        stored: Option<Uint128>,
+    },
+}
+
+/// Scores kept alongside a `(u128::MAX - score, address)` index, so the index iterates
+/// highest score first with ties ordered by address. Pages resume after the `(score,
+/// address)` pair of their last entry, which stays stable when other scores change
+pub struct Leaderboard {
+    scores: Map<'static, Addr, Uint128>,
+    index: Map<'static, (u128, Addr), Empty>,
+}
+
+impl Leaderboard {
+    pub const fn new(scores: &'static str, index: &'static str) -> Self {
+        Leaderboard {
+            scores: Map::new(scores),
+            index: Map::new(index),
+        }
+    }
+
+    pub fn score(&self, storage: &dyn Storage, address: &Addr) -> StdResult<Option<Uint128>> {
+        self.scores.may_load(storage, address.clone())
+    }
+
+    /// Sets the score of `address`, moving its index entry
+    pub fn save(&self, storage: &mut dyn Storage, address: &Addr, score: Uint128) -> StdResult<()> {
+        self.remove(storage, address)?;
+        self.scores.save(storage, address.clone(), &score)?;
+        self.index
+            .save(storage, (inverted(score), address.clone()), &Empty {})
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage, address: &Addr) -> StdResult<()> {
+        if let Some(score) = self.score(storage, address)? {
+            self.scores.remove(storage, address.clone());
+            self.index
+                .remove(storage, (inverted(score), address.clone()));
+        }
+        Ok(())
+    }
+
+    /// Highest scores first, the cursor is the score and address of the last entry
+    pub fn page<const LIMIT: usize>(
+        &self,
+        storage: &dyn Storage,
+        page: Page<LIMIT, (Uint128, Addr)>,
+    ) -> StdResult<NextPage<Ranking, (Uint128, Addr)>> {
+        let range = self.index.keys(
+            storage,
+            page.start.map(|(score, address)| {
+                Bound::Exclusive(((inverted(score), address), PhantomData))
+            }),
+            None,
+            Order::Ascending,
+        );
+
+        let res = collect(
+            range.map(|key| key.map(|key| (key, ()))),
+            page.qty.unwrap_or(LIMIT),
+            |(score, address), _| Ranking {
+                address,
+                score: from_inverted(score),
+            },
+        )?;
+        Ok(map_cursor(res, |(score, address)| {
+            (from_inverted(score), address)
+        }))
+    }
+
+    /// Checks a page of scores for missing index entries, e.g. after a migration. `next`
+    /// is only set while there are scores left to check, `scanned` counts the checked ones
+    pub fn verify_scores<const LIMIT: usize>(
+        &self,
+        storage: &dyn Storage,
+        page: Page<LIMIT, Addr>,
+    ) -> StdResult<NextPage<IndexIssue, Addr>> {
+        let limit = page.qty.unwrap_or(LIMIT);
+        let range = self.scores.range(
+            storage,
+            page.start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        );
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range.take(limit) {
+            let (address, score) = item?;
+            if !self.index.has(storage, (inverted(score), address.clone())) {
+                data.push(IndexIssue::MissingIndex {
+                    address: address.clone(),
+                    score,
+                });
+            }
+            scanned += 1;
+            end = Some(address);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end.filter(|_| scanned == limit),
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+
+    /// Checks a page of index entries for scores that are gone or differ, the cursor is
+    /// the same as [`Leaderboard::page`]
+    pub fn verify_index<const LIMIT: usize>(
+        &self,
+        storage: &dyn Storage,
+        page: Page<LIMIT, (Uint128, Addr)>,
+    ) -> StdResult<NextPage<IndexIssue, (Uint128, Addr)>> {
+        let limit = page.qty.unwrap_or(LIMIT);
+        let range = self.index.keys(
+            storage,
+            page.start.map(|(score, address)| {
+                Bound::Exclusive(((inverted(score), address), PhantomData))
+            }),
+            None,
+            Order::Ascending,
+        );
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for key in range.take(limit) {
+            let (key, address) = key?;
+            let score = from_inverted(key);
+            let stored = self.score(storage, &address)?;
+            if stored != Some(score) {
+                data.push(IndexIssue::StaleIndex {
+                    address: address.clone(),
+                    score,
+                    stored,
+                });
+            }
+            scanned += 1;
+            end = Some((score, address));
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end.filter(|_| scanned == limit),
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+fn inverted(score: Uint128) -> u128 {
+    u128::MAX - score.u128()
+}
+
+fn from_inverted(key: u128) -> Uint128 {
+    Uint128::new(u128::MAX - key)
+}
+
+fn map_cursor<D, K, C>(page: NextPage<D, K>, cursor: impl FnOnce(K) -> C) -> NextPage<D, C> {
+    NextPage {
+        data: page.data,
+        next: page.next.map(cursor),
+        qty: page.qty,
+        scanned: page.scanned,
+        errors: page.errors,
+    }
+}
+
+fn collect<K, V, Data>(
+    range: impl Iterator<Item = StdResult<(K, V)>>,
+    limit: usize,
+    transform: impl Fn(K, V) -> Data,
+) -> StdResult<NextPage<Data, K>>
+where
+    K: Clone,
+{
+    let mut data = vec![];
+    let mut end = None;
+
+    for item in range.take(limit) {
+        let (key, value) = item?;
+        end = Some(key.clone());
+        data.push(transform(key, value));
+    }
+
+    let len = data.len();
+    Ok(NextPage {
+        data,
+        next: end,
+        qty: len,
+        scanned: len,
+        errors: vec![],
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::recipes::{
+        all_allowances, allowances, approvals, balances, tokens_by_owner, Allowance, Approval,
+        Balance, IndexIssue, Leaderboard, Ranking,
+    };
+    use crate::{IndexedMap, Map, MultiIndex, Page};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{Addr, Empty, Uint128};
+    use cw_storage_plus::{Index, IndexList};
+
+    #[cw_serde]
+    struct TokenInfo {
+        owner: Addr,
+    }
+
+    struct TokenIndexes<'a> {
+        owner: MultiIndex<'a, Addr, TokenInfo, String>,
+    }
+
+    impl<'a> IndexList<TokenInfo> for TokenIndexes<'a> {
+        fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo>> + '_> {
+            let v: Vec<&dyn Index<TokenInfo>> = vec![&self.owner];
+            Box::new(v.into_iter())
+        }
+    }
+
+    fn tokens<'a>() -> IndexedMap<'static, &'static str, TokenInfo, TokenIndexes<'a>> {
+        IndexedMap::new(
+            "tokens",
+            TokenIndexes {
+                owner: MultiIndex::new(|_, t| t.owner.clone(), "tokens", "tokens__owner"),
+            },
+        )
+    }
+
+    #[test]
+    fn balances_recipe() {
+        let mut deps = mock_dependencies();
+        let map: Map<&Addr, Uint128> = Map::new("balance");
+
+        for i in 0..30u128 {
+            let addr = Addr::unchecked(format!("addr{:02}", i));
+            map.save(deps.as_mut().storage, &addr, &Uint128::new(i))
+                .unwrap();
+        }
+
+        let page: Page<10, _> = Page {
+            start: Some(Addr::unchecked("addr04")),
+            qty: Some(2),
+        };
+        let res = balances(deps.as_ref(), &map, page).unwrap();
+        assert_eq!(
+            res.data,
+            vec![
+                Balance {
+                    address: Addr::unchecked("addr05"),
+                    amount: Uint128::new(5)
+                },
+                Balance {
+                    address: Addr::unchecked("addr06"),
+                    amount: Uint128::new(6)
+                }
+            ]
+        );
+        assert_eq!(res.next, Some(Addr::unchecked("addr06")));
+    }
+
+    #[test]
+    fn tokens_by_owner_recipe() {
+        let mut deps = mock_dependencies();
+        let map = tokens();
+
+        for i in 0..30 {
+            let info = TokenInfo {
+                owner: Addr::unchecked(format!("owner{}", i % 3)),
+            };
+            map.save(deps.as_mut().storage, &format!("token{:02}", i), &info)
+                .unwrap();
+        }
+
+        let page: Page<4, _> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = tokens_by_owner(
+            deps.as_ref(),
+            &map.idx.owner,
+            Addr::unchecked("owner1"),
+            page,
+        )
+        .unwrap();
+        assert_eq!(res.data, vec!["token01", "token04", "token07", "token10"]);
+
+        let page: Page<4, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = tokens_by_owner(
+            deps.as_ref(),
+            &map.idx.owner,
+            Addr::unchecked("owner1"),
+            page,
+        )
+        .unwrap();
+        assert_eq!(res.data, vec!["token13", "token16", "token19", "token22"]);
+        assert_eq!(res.next, Some("token22".to_string()));
+    }
+
+    #[test]
+    fn approvals_recipe() {
+        let mut deps = mock_dependencies();
+        let map: Map<(&str, &Addr), u64> = Map::new("approvals");
+
+        for i in 0..10u64 {
+            let spender = Addr::unchecked(format!("spender{}", i));
+            map.save(deps.as_mut().storage, ("token1", &spender), &i)
+                .unwrap();
+            map.save(deps.as_mut().storage, ("token2", &spender), &(i + 100))
+                .unwrap();
+        }
+
+        let page: Page<3, _> = Page {
+            start: Some(Addr::unchecked("spender6")),
+            qty: None,
+        };
+        let res = approvals(deps.as_ref(), &map, "token2", page).unwrap();
+        assert_eq!(
+            res.data,
+            vec![
+                Approval {
+                    spender: Addr::unchecked("spender7"),
+                    info: 107
+                },
+                Approval {
+                    spender: Addr::unchecked("spender8"),
+                    info: 108
+                },
+                Approval {
+                    spender: Addr::unchecked("spender9"),
+                    info: 109
+                }
+            ]
+        );
+        assert_eq!(res.next, Some(Addr::unchecked("spender9")));
+    }
+
+    #[test]
+    fn allowances_recipe() {
+        let mut deps = mock_dependencies();
+        let map: Map<(&Addr, &Addr), Uint128> = Map::new("allowance");
+        let owners: Vec<_> = (0..2)
+            .map(|i| deps.api.addr_make(&format!("owner{}", i)))
+            .collect();
+        let mut spenders: Vec<_> = (0..5)
+            .map(|i| deps.api.addr_make(&format!("spender{}", i)))
+            .collect();
+        spenders.sort();
+
+        for owner in &owners {
+            for (i, spender) in spenders.iter().enumerate() {
+                map.save(
+                    deps.as_mut().storage,
+                    (owner, spender),
+                    &Uint128::new(i as u128),
+                )
+                .unwrap();
+            }
+        }
+
+        let page: Page<2, _> = Page {
+            start: Some(spenders[1].to_string()),
+            qty: None,
+        };
+        let res = allowances(deps.as_ref(), &map, owners[1].as_str(), page).unwrap();
+        assert_eq!(
+            res.data,
+            vec![
+                Allowance {
+                    owner: owners[1].clone(),
+                    spender: spenders[2].clone(),
+                    info: Uint128::new(2)
+                },
+                Allowance {
+                    owner: owners[1].clone(),
+                    spender: spenders[3].clone(),
+                    info: Uint128::new(3)
+                }
+            ]
+        );
+        assert_eq!(res.next, Some(spenders[3].to_string()));
+
+        let page: Page<2, _> = Page {
+            start: None,
+            qty: None,
+        };
+        assert!(allowances(deps.as_ref(), &map, "Owner", page).is_err());
+
+        // Walking every pair crosses from one owner to the next
+        let mut pairs = vec![];
+        let mut start = None;
+        loop {
+            let page: Page<3, (String, String)> = Page { start, qty: None };
+            let res = all_allowances(deps.as_ref(), &map, page).unwrap();
+            pairs.extend(res.data.into_iter().map(|a| (a.owner, a.spender)));
+            if res.qty < 3 {
+                break;
+            }
+            start = res.next;
+        }
+        let mut expected = vec![];
+        for owner in &owners {
+            for spender in &spenders {
+                expected.push((owner.clone(), spender.clone()));
+            }
+        }
+        expected.sort();
+        assert_eq!(pairs, expected);
+
+        let page: Page<3, _> = Page {
+            start: Some((owners[0].to_string(), "Spender".to_string())),
+            qty: None,
+        };
+        assert!(all_allowances(deps.as_ref(), &map, page).is_err());
+    }
+
+    #[test]
+    fn leaderboard_recipe() {
+        let mut deps = mock_dependencies();
+        let board = Leaderboard::new("scores", "scores__rank");
+        let player = |name: &str| Addr::unchecked(name);
+
+        for (name, score) in [("alice", 50), ("bob", 80), ("carol", 50), ("dave", 10)] {
+            board
+                .save(deps.as_mut().storage, &player(name), Uint128::new(score))
+                .unwrap();
+        }
+        board
+            .save(deps.as_mut().storage, &player("dave"), Uint128::new(60))
+            .unwrap();
+
+        let page: Page<3, _> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = board.page(deps.as_ref().storage, page).unwrap();
+        let ranking = |name: &str, score: u128| Ranking {
+            address: player(name),
+            score: Uint128::new(score),
+        };
+        assert_eq!(
+            res.data,
+            vec![
+                ranking("bob", 80),
+                ranking("dave", 60),
+                ranking("alice", 50)
+            ]
+        );
+        assert_eq!(res.next, Some((Uint128::new(50), player("alice"))));
+
+        // Ties resume by address, even when scores above the cursor change
+        board.remove(deps.as_mut().storage, &player("bob")).unwrap();
+        let page: Page<3, _> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = board.page(deps.as_ref().storage, page).unwrap();
+        assert_eq!(res.data, vec![ranking("carol", 50)]);
+        assert_eq!(
+            board.score(deps.as_ref().storage, &player("bob")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn leaderboard_verify() {
+        let mut deps = mock_dependencies();
+        let board = Leaderboard::new("scores", "scores__rank");
+        let index: Map<(u128, Addr), Empty> = Map::new("scores__rank");
+
+        for i in 0..5u128 {
+            let player = Addr::unchecked(format!("player{}", i));
+            board
+                .save(deps.as_mut().storage, &player, Uint128::new(i * 10))
+                .unwrap();
+        }
+
+        // Writes that bypassed the leaderboard
+        let player = Addr::unchecked("player1");
+        index.remove(deps.as_mut().storage, (u128::MAX - 10, player.clone()));
+        index
+            .save(
+                deps.as_mut().storage,
+                (u128::MAX - 99, player.clone()),
+                &Empty {},
+            )
+            .unwrap();
+
+        let page: Page<3, Addr> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = board.verify_scores(deps.as_ref().storage, page).unwrap();
+        assert_eq!(
+            res.data,
+            vec![IndexIssue::MissingIndex {
+                address: player.clone(),
+                score: Uint128::new(10)
+            }]
+        );
+        assert_eq!(res.scanned, 3);
+        assert_eq!(res.next, Some(Addr::unchecked("player2")));
+
+        let page: Page<3, Addr> = Page {
+            start: res.next,
+            qty: None,
+        };
+        let res = board.verify_scores(deps.as_ref().storage, page).unwrap();
+        assert!(res.data.is_empty());
+        assert_eq!(res.next, None);
+
+        let page: Page<10, (Uint128, Addr)> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = board.verify_index(deps.as_ref().storage, page).unwrap();
+        assert_eq!(
+            res.data,
+            vec![IndexIssue::StaleIndex {
+                address: player,
+                score: Uint128::new(99),
+                stored: Some(Uint128::new(10))
+            }]
+        );
+        assert_eq!(res.scanned, 5);
+    }
+}
@@ -0,0 +1,148 @@
+use crate::{namespace, raw_range, Map, NextPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Order, Storage};
+use std::collections::BTreeMap;
+
+/// Maps exposed by name through a single [`RegistryPage`] query, giving contracts a
+/// uniform debug and export endpoint without writing a handler per map
+#[derive(Default)]
+pub struct MapRegistry {
+    maps: BTreeMap<String, Vec<u8>>,
+}
+
+impl MapRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<K, V>(mut self, name: impl Into<String>, map: &Map<'_, K, V>) -> Self {
+        self.maps.insert(name.into(), namespace(map).to_vec());
+        self
+    }
+
+    /// Registered map names, in alphabetical order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.maps.keys().map(String::as_str)
+    }
+}
+
+pub type DefaultRegistryPage = RegistryPage<50>;
+
+/// Raw page over the map registered as `map`, the cursor is the raw key of the last entry
+#[cw_serde]
+pub struct RegistryPage<const LIMIT: usize> {
+    pub map: String,
+    pub cursor: Option<Binary>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize> RegistryPage<LIMIT> {
+    pub fn into_registry_pagination(
+        self,
+        storage: &dyn Storage,
+        registry: &MapRegistry,
+    ) -> Result<NextPage<(Binary, Binary), Binary>, QueryError> {
+        let namespace = registry
+            .maps
+            .get(&self.map)
+            .ok_or_else(|| QueryError::UnknownMap {
+                name: self.map.clone(),
+            })?;
+
+        let range = raw_range(
+            storage,
+            namespace,
+            &[],
+            self.cursor.map(|c| c.to_vec()),
+            Order::Ascending,
+        )
+        .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for (raw_key, raw_value) in range {
+            let key: Binary = raw_key.into();
+            data.push((key.clone(), raw_value.into()));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, MapRegistry, RegistryPage};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::{to_json_binary, Binary};
+
+    #[test]
+    fn into_registry_pagination() {
+        let mut deps = mock_dependencies();
+        let balances: Map<u8, u64> = Map::new("balances");
+        let names: Map<String, String> = Map::new("names");
+
+        for i in 0..30 {
+            balances
+                .save(deps.as_mut().storage, i, &(i as u64 * 10))
+                .unwrap();
+            names
+                .save(
+                    deps.as_mut().storage,
+                    format!("name-{:02}", i),
+                    &i.to_string(),
+                )
+                .unwrap();
+        }
+
+        let registry = MapRegistry::new()
+            .register("names", &names)
+            .register("balances", &balances);
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec!["balances", "names"]
+        );
+
+        let query: RegistryPage<20> = RegistryPage {
+            map: "balances".to_string(),
+            cursor: None,
+            qty: Some(5),
+        };
+        let res = query
+            .into_registry_pagination(deps.as_ref().storage, &registry)
+            .unwrap();
+        assert_eq!(res.qty, 5);
+        assert_eq!(
+            res.data.get(1).unwrap(),
+            &(Binary::from(vec![1u8]), to_json_binary(&10u64).unwrap())
+        );
+        assert_eq!(res.next, Some(Binary::from(vec![4u8])));
+
+        let query: RegistryPage<20> = RegistryPage {
+            map: "names".to_string(),
+            cursor: Some(Binary::from(b"name-27".to_vec())),
+            qty: None,
+        };
+        let res = query
+            .into_registry_pagination(deps.as_ref().storage, &registry)
+            .unwrap();
+        assert_eq!(res.qty, 2);
+        assert_eq!(res.next, Some(Binary::from(b"name-29".to_vec())));
+
+        let query: RegistryPage<20> = RegistryPage {
+            map: "missing".to_string(),
+            cursor: None,
+            qty: None,
+        };
+        assert!(query
+            .into_registry_pagination(deps.as_ref().storage, &registry)
+            .is_err());
+    }
+}
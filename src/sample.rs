@@ -0,0 +1,223 @@
+use crate::{Map, NextPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Binary, Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultSamplePage<K> = SamplePage<50, 500, K>;
+
+/// Pseudo-random sample of `count` entries out of at most `MAX_SCAN` scanned, for audits
+/// and spot checks that shouldn't export the whole map. The sample is deterministic for a
+/// given `seed`, usually a block hash supplied by the caller, and returned in key order
+#[cw_serde]
+pub struct SamplePage<const LIMIT: usize, const MAX_SCAN: usize, K> {
+    pub start: Option<K>,
+    pub seed: Binary,
+    pub count: Option<usize>,
+    pub max_scan: Option<usize>,
+}
+
+impl<const LIMIT: usize, const MAX_SCAN: usize, Key> SamplePage<LIMIT, MAX_SCAN, Key> {
+    pub fn into_sample_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let count = self.count.unwrap_or(LIMIT).min(LIMIT);
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(max_scan);
+        let mut rng = SplitMix::new(self.seed.as_slice());
+        // Reservoir sampling, each slot keeps the scan position to restore key order
+        let mut reservoir = Vec::with_capacity(count);
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            let (key, value) = item?;
+            if reservoir.len() < count {
+                reservoir.push((scanned, transform(&key, value)));
+            } else {
+                let slot = (rng.next_u64() % (scanned as u64 + 1)) as usize;
+                if slot < count {
+                    reservoir[slot] = (scanned, transform(&key, value));
+                }
+            }
+            scanned += 1;
+            end = Some(key);
+        }
+
+        reservoir.sort_by_key(|(position, _)| *position);
+        let data: Vec<_> = reservoir.into_iter().map(|(_, data)| data).collect();
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+/// Every `stride`th key after `start`, at most `limit` of them, for spot audits that must
+/// be reproducible. Reads up to `stride * limit` keys, `next` is the last key read so
+/// following pages keep the same spacing
+pub fn nth_keys<'a, K, V>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, K, V>,
+    start: Option<K>,
+    stride: usize,
+    limit: usize,
+) -> Result<NextPage<K::Output, K::Output>, QueryError>
+where
+    K: PrimaryKey<'a> + KeyDeserialize,
+    K::Output: Clone + 'static,
+    V: Serialize + DeserializeOwned,
+{
+    if stride == 0 {
+        return Err(QueryError::ZeroLimit {});
+    }
+
+    let keys = map
+        .keys(
+            storage,
+            start.map(|s| Bound::Exclusive((s, PhantomData))),
+            None,
+            Order::Ascending,
+        )
+        .take(stride.saturating_mul(limit));
+    let mut data = vec![];
+    let mut end = None;
+    let mut scanned = 0;
+
+    for key in keys {
+        let key = key?;
+        if scanned % stride == 0 {
+            data.push(key.clone());
+        }
+        scanned += 1;
+        end = Some(key);
+    }
+
+    let len = data.len();
+    Ok(NextPage {
+        next: end,
+        data,
+        qty: len,
+        scanned,
+        errors: vec![],
+    })
+}
+
+/// 64 bit FNV-1a hash, stable across releases since it decides samples and shards
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Splitmix64 seeded with the FNV-1a hash of the seed bytes, enough for sampling and
+/// cheap to run on chain
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn new(seed: &[u8]) -> Self {
+        SplitMix(fnv1a(seed))
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{nth_keys, Map, QueryError, SamplePage};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Binary;
+
+    #[test]
+    fn into_sample_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let sample = |seed: &[u8]| {
+            let query: SamplePage<5, 100, u8> = SamplePage {
+                start: None,
+                seed: Binary::from(seed),
+                count: None,
+                max_scan: None,
+            };
+            query
+                .into_sample_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+                .unwrap()
+        };
+
+        let res = sample(b"block-1");
+        assert_eq!(res.data, vec![4, 21, 44, 58, 83]);
+        assert_eq!(res.next, Some(99));
+        assert_eq!(res.scanned, 100);
+        assert_eq!(sample(b"block-1").data, res.data);
+        assert_eq!(sample(b"block-2").data, vec![14, 31, 34, 38, 92]);
+
+        // Scanning no more than `count` entries returns all of them
+        let query: SamplePage<5, 100, u8> = SamplePage {
+            start: Some(95),
+            seed: Binary::from(b"block-1".as_slice()),
+            count: Some(10),
+            max_scan: None,
+        };
+        let res = query
+            .into_sample_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![96, 97, 98, 99]);
+    }
+
+    #[test]
+    fn nth_keys_stride() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        let res = nth_keys(deps.as_ref().storage, &test_map, None, 10, 3).unwrap();
+        assert_eq!(res.data, vec![0, 10, 20]);
+        assert_eq!(res.next, Some(29));
+        assert_eq!(res.scanned, 30);
+
+        let res = nth_keys(deps.as_ref().storage, &test_map, res.next, 10, 10).unwrap();
+        assert_eq!(res.data, vec![30, 40, 50, 60, 70, 80, 90]);
+        assert_eq!(res.next, Some(99));
+
+        assert_eq!(
+            nth_keys(deps.as_ref().storage, &test_map, None, 0, 3).unwrap_err(),
+            QueryError::ZeroLimit {}
+        );
+    }
+}
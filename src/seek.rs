@@ -0,0 +1,101 @@
+use crate::{Map, NextPage, Page};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+#[cw_serde]
+pub struct SeekNextPage<D, K> {
+    pub page: NextPage<D, K>,
+    /// The seek key is stored in the map and is the first entry of the page
+    pub found: bool,
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Jumps to `start` instead of resuming after it, so the key is the first entry of
+    /// the page when present. Following pages should go through `into_pagination`
+    pub fn into_seek_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<SeekNextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let found = match &self.start {
+            Some(start) => map.has(storage, start.clone()),
+            None => false,
+        };
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Inclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+
+        for item in range {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(SeekNextPage {
+            page: NextPage {
+                data,
+                next: end,
+                qty: len,
+                scanned: len,
+                errors: vec![],
+            },
+            found,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_seek_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u8> = Map::new("test_map");
+
+        for i in 0..50 {
+            test_map.save(deps.as_mut().storage, i * 2, &i).unwrap();
+        }
+
+        let query: Page<3, _> = Page {
+            start: Some(10),
+            qty: None,
+        };
+        let res = query
+            .into_seek_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert!(res.found);
+        assert_eq!(res.page.data, vec![10, 12, 14]);
+        assert_eq!(res.page.next, Some(14));
+
+        let query: Page<3, _> = Page {
+            start: Some(11),
+            qty: None,
+        };
+        let res = query
+            .into_seek_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert!(!res.found);
+        assert_eq!(res.page.data, vec![12, 14, 16]);
+    }
+}
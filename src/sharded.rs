@@ -0,0 +1,147 @@
+use crate::error::deserialize_key;
+use crate::sample::fnv1a;
+use crate::{Map, NextPage, QueryError};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdError, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultShardedPage<K> = ShardedPage<50, 500, K>;
+
+/// Page over the keys of a single `shard`, so several workers can export a map
+/// concurrently without overlapping. Every worker still scans the whole map, at most
+/// `MAX_SCAN` entries per page, and keeps following `next` until it is `None`
+#[cw_serde]
+pub struct ShardedPage<const LIMIT: usize, const MAX_SCAN: usize, K> {
+    pub shard: u32,
+    pub total_shards: u32,
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    pub max_scan: Option<usize>,
+}
+
+/// Shard a raw key belongs to, the FNV-1a hash of the key modulo `total_shards`
+pub fn key_shard(raw_key: &[u8], total_shards: u32) -> u32 {
+    (fnv1a(raw_key) % u64::from(total_shards.max(1))) as u32
+}
+
+impl<const LIMIT: usize, const MAX_SCAN: usize, Key> ShardedPage<LIMIT, MAX_SCAN, Key> {
+    pub fn into_sharded_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> Result<NextPage<Data, Key::Output>, QueryError>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        if self.shard >= self.total_shards {
+            return Err(StdError::generic_err(format!(
+                "Shard {} is out of {} shards",
+                self.shard, self.total_shards
+            ))
+            .into());
+        }
+
+        let limit = self.qty.unwrap_or(LIMIT);
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let range = map
+            .range_raw(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(max_scan);
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            if data.len() >= limit {
+                break;
+            }
+
+            let (raw_key, value) = item?;
+            scanned += 1;
+            let in_shard = key_shard(&raw_key, self.total_shards) == self.shard;
+            let key = deserialize_key::<Key>(raw_key)?;
+            if in_shard {
+                data.push(transform(&key, value));
+            }
+
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{key_shard, Map, ShardedPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_sharded_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+
+        for i in 0..100 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+
+        // Every key is exported by exactly one of the workers
+        let mut exported = vec![];
+        for shard in 0..3 {
+            let mut start = None;
+            loop {
+                let query: ShardedPage<10, 40, u32> = ShardedPage {
+                    shard,
+                    total_shards: 3,
+                    start,
+                    qty: None,
+                    max_scan: None,
+                };
+                let res = query
+                    .into_sharded_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+                    .unwrap();
+                assert!(res.scanned <= 40);
+                assert!(res
+                    .data
+                    .iter()
+                    .all(|v| key_shard(&v.to_be_bytes(), 3) == shard));
+                exported.extend(res.data);
+                if res.next.is_none() {
+                    break;
+                }
+                start = res.next;
+            }
+        }
+        exported.sort();
+        assert_eq!(exported, (0..100).collect::<Vec<_>>());
+
+        let query: ShardedPage<10, 40, u32> = ShardedPage {
+            shard: 3,
+            total_shards: 3,
+            start: None,
+            qty: None,
+            max_scan: None,
+        };
+        assert!(query
+            .into_sharded_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .is_err());
+    }
+}
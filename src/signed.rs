@@ -0,0 +1,195 @@
+use crate::{NextPage, QueryError};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_json, to_json_vec, Binary, StdResult};
+use sha2::{Digest, Sha256};
+
+const MAC_LEN: usize = 32;
+const STAMP_LEN: usize = 8;
+
+/// Same as [`crate::encode_cursor`] but also stamps the cursor with `stamp`, usually the
+/// block height it was issued at or a nonce of the caller. The token closes with an
+/// HMAC-SHA256 keyed with `secret`, a value only the contract knows, so clients can't
+/// forge or restamp cursors to get past [`decode_fresh_cursor`]
+pub fn encode_stamped_cursor<K: Serialize>(
+    version: u8,
+    stamp: u64,
+    secret: &[u8],
+    cursor: &K,
+) -> StdResult<Binary> {
+    let mut token = vec![version];
+    token.extend(stamp.to_be_bytes());
+    token.extend(to_json_vec(cursor)?);
+    let mac = hmac_sha256(secret, &token);
+    token.extend(mac);
+    Ok(token.into())
+}
+
+/// Decodes a cursor from [`encode_stamped_cursor`] along with its stamp, for endpoints
+/// binding cursors to a nonce. The signature is checked before anything else is read
+pub fn decode_stamped_cursor<K: DeserializeOwned>(
+    version: u8,
+    secret: &[u8],
+    token: &[u8],
+) -> Result<(K, u64), QueryError> {
+    if token.len() < 1 + STAMP_LEN + MAC_LEN {
+        return Err(QueryError::CursorDecode {
+            msg: "missing cursor stamp or signature".to_string(),
+        });
+    }
+    let (payload, mac) = token.split_at(token.len() - MAC_LEN);
+    // Compare every byte so the time taken doesn't hint at how much of the mac matched
+    let diff = mac
+        .iter()
+        .zip(hmac_sha256(secret, payload))
+        .fold(0, |acc, (a, b)| acc | (a ^ b));
+    if diff != 0 {
+        return Err(QueryError::CursorSignature {});
+    }
+
+    let (found, payload) = payload.split_first().unwrap();
+    if *found != version {
+        return Err(QueryError::CursorVersion {
+            expected: version,
+            found: *found,
+        });
+    }
+    let (stamp, cursor) = payload.split_at(STAMP_LEN);
+    let stamp = u64::from_be_bytes(stamp.try_into().unwrap());
+    let cursor = from_json(cursor).map_err(|e| QueryError::CursorDecode { msg: e.to_string() })?;
+    Ok((cursor, stamp))
+}
+
+/// Decodes a cursor stamped with its block height, rejecting cursors issued more than
+/// `max_age` blocks before `height`
+pub fn decode_fresh_cursor<K: DeserializeOwned>(
+    version: u8,
+    secret: &[u8],
+    token: &[u8],
+    height: u64,
+    max_age: u64,
+) -> Result<K, QueryError> {
+    let (cursor, issued) = decode_stamped_cursor(version, secret, token)?;
+    if height.saturating_sub(issued) > max_age {
+        return Err(QueryError::CursorExpired { issued, max_age });
+    }
+    Ok(cursor)
+}
+
+/// HMAC-SHA256 as in RFC 2104, so signatures can be checked with any off-chain library
+fn hmac_sha256(secret: &[u8], payload: &[u8]) -> [u8; MAC_LEN] {
+    let mut key = [0u8; 64];
+    if secret.len() > key.len() {
+        key[..MAC_LEN].copy_from_slice(&Sha256::digest(secret));
+    } else {
+        key[..secret.len()].copy_from_slice(secret);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(key.map(|b| b ^ 0x36));
+    inner.update(payload);
+    let mut outer = Sha256::new();
+    outer.update(key.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+impl<D, K: Serialize> NextPage<D, K> {
+    /// Opaque cursor for the next page stamped with `stamp` and signed with `secret`, see
+    /// [`encode_stamped_cursor`]
+    pub fn encode_stamped_next(
+        &self,
+        version: u8,
+        stamp: u64,
+        secret: &[u8],
+    ) -> StdResult<Option<Binary>> {
+        self.next
+            .as_ref()
+            .map(|next| encode_stamped_cursor(version, stamp, secret, next))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::hmac_sha256;
+    use crate::{
+        decode_fresh_cursor, decode_stamped_cursor, encode_stamped_cursor, NextPage, QueryError,
+    };
+    use cosmwasm_std::to_json_vec;
+
+    const SECRET: &[u8] = b"contract secret";
+
+    #[test]
+    fn hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            [
+                0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95,
+                0x75, 0xc7, 0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9,
+                0x64, 0xec, 0x38, 0x43
+            ]
+        );
+    }
+
+    #[test]
+    fn stamped_cursor() {
+        let page = NextPage {
+            data: vec![1, 2, 3],
+            next: Some("addr".to_string()),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+        let token = page.encode_stamped_next(1, 100, SECRET).unwrap().unwrap();
+        assert_eq!(
+            decode_stamped_cursor::<String>(1, SECRET, &token).unwrap(),
+            ("addr".to_string(), 100)
+        );
+        assert_eq!(
+            decode_fresh_cursor::<String>(1, SECRET, &token, 110, 10).unwrap(),
+            "addr"
+        );
+        assert_eq!(
+            decode_fresh_cursor::<String>(1, SECRET, &token, 111, 10).unwrap_err(),
+            QueryError::CursorExpired {
+                issued: 100,
+                max_age: 10
+            }
+        );
+        assert!(matches!(
+            decode_stamped_cursor::<String>(1, SECRET, &[1, 0, 0]),
+            Err(QueryError::CursorDecode { .. })
+        ));
+    }
+
+    #[test]
+    fn forged_cursor() {
+        let token = encode_stamped_cursor(1, 100, SECRET, &"addr".to_string()).unwrap();
+
+        // Restamping the cursor to a later height breaks the signature
+        let mut restamped = token.to_vec();
+        restamped[1..9].copy_from_slice(&200u64.to_be_bytes());
+        assert_eq!(
+            decode_fresh_cursor::<String>(1, SECRET, &restamped, 210, 10).unwrap_err(),
+            QueryError::CursorSignature {}
+        );
+
+        // So does building one from scratch without the secret
+        let mut forged = vec![1];
+        forged.extend(200u64.to_be_bytes());
+        forged.extend(to_json_vec(&"addr").unwrap());
+        forged.extend([0; 32]);
+        assert_eq!(
+            decode_fresh_cursor::<String>(1, SECRET, &forged, 210, 10).unwrap_err(),
+            QueryError::CursorSignature {}
+        );
+
+        let token = encode_stamped_cursor(1, 100, b"other secret", &"addr".to_string()).unwrap();
+        assert_eq!(
+            decode_stamped_cursor::<String>(1, SECRET, &token).unwrap_err(),
+            QueryError::CursorSignature {}
+        );
+    }
+}
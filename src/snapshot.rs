@@ -0,0 +1,291 @@
+use crate::{NextPage, SnapshotMap};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, Prefixer, PrimaryKey};
+
+pub type DefaultSnapshotDiffPage<K> = SnapshotDiffPage<50, K>;
+
+#[cw_serde]
+pub struct SnapshotDiff<K, V> {
+    pub key: K,
+    /// Value at height `from`
+    pub old: Option<V>,
+    /// Value at height `to`
+    pub new: Option<V>,
+}
+
+/// Keys of a `SnapshotMap` whose value differs between the `from` and `to` heights, so
+/// indexers can sync incrementally. Only keys with a changelog are scanned, so the map
+/// must use a strategy that records every change. Keys changed and then reverted in
+/// between are left out but still use up the requested `qty`, count towards `scanned`
+/// and advance the cursor
+#[cw_serde]
+pub struct SnapshotDiffPage<const LIMIT: usize, K> {
+    pub from: u64,
+    pub to: u64,
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key> SnapshotDiffPage<LIMIT, Key> {
+    pub fn into_snapshot_diff_pagination<'a, Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &SnapshotMap<'static, Key, Value>,
+    ) -> StdResult<NextPage<SnapshotDiff<Key, Value>, Key>>
+    where
+        Key: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize<Output = Key> + Clone + 'static,
+        Value: Serialize + DeserializeOwned + Clone + PartialEq,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let mut seek = self.start;
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        while scanned < limit {
+            // Skip the remaining heights of the last key in a single seek
+            let min = seek.map(|key| Bound::exclusive((key, u64::MAX)));
+            let Some(item) = map
+                .changelog()
+                .keys(storage, min, None, Order::Ascending)
+                .next()
+            else {
+                break;
+            };
+            let (key, _) = item?;
+            scanned += 1;
+
+            let old = map.may_load_at_height(storage, key.clone(), self.from)?;
+            let new = map.may_load_at_height(storage, key.clone(), self.to)?;
+            if old != new {
+                data.push(SnapshotDiff {
+                    key: key.clone(),
+                    old,
+                    new,
+                });
+            }
+
+            seek = Some(key.clone());
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+pub type DefaultSnapshotOverlayPage<K> = SnapshotOverlayPage<50, K>;
+
+#[cw_serde]
+pub struct SnapshotOverlay<K, V> {
+    pub key: K,
+    /// Value at the page's `height`
+    pub snapshot: Option<V>,
+    /// Current value, differs from `snapshot` for keys changed since
+    pub live: Option<V>,
+}
+
+/// Every key of a `SnapshotMap` with its value at `height` next to its live value, e.g.
+/// voting power as of a proposal start alongside the current one. Keys are read from the
+/// changelog like [`SnapshotDiffPage`], so the map must use a strategy that records every
+/// change. Keys absent at both heights are left out but still use up the requested `qty`
+/// and count towards `scanned`
+#[cw_serde]
+pub struct SnapshotOverlayPage<const LIMIT: usize, K> {
+    pub height: u64,
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key> SnapshotOverlayPage<LIMIT, Key> {
+    pub fn into_snapshot_overlay_pagination<'a, Value>(
+        self,
+        storage: &'a dyn Storage,
+        map: &SnapshotMap<'static, Key, Value>,
+    ) -> StdResult<NextPage<SnapshotOverlay<Key, Value>, Key>>
+    where
+        Key: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize<Output = Key> + Clone + 'static,
+        Value: Serialize + DeserializeOwned + Clone,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let mut seek = self.start;
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        while scanned < limit {
+            let min = seek.map(|key| Bound::exclusive((key, u64::MAX)));
+            let Some(item) = map
+                .changelog()
+                .keys(storage, min, None, Order::Ascending)
+                .next()
+            else {
+                break;
+            };
+            let (key, _) = item?;
+            scanned += 1;
+
+            let snapshot = map.may_load_at_height(storage, key.clone(), self.height)?;
+            let live = map.may_load(storage, key.clone())?;
+            if snapshot.is_some() || live.is_some() {
+                data.push(SnapshotOverlay {
+                    key: key.clone(),
+                    snapshot,
+                    live,
+                });
+            }
+
+            seek = Some(key.clone());
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        SnapshotDiff, SnapshotDiffPage, SnapshotMap, SnapshotOverlay, SnapshotOverlayPage,
+    };
+    use cosmwasm_std::testing::mock_dependencies;
+    use cw_storage_plus::Strategy;
+
+    #[test]
+    fn into_snapshot_diff_pagination() {
+        let mut deps = mock_dependencies();
+        let balances: SnapshotMap<u32, u64> = SnapshotMap::new(
+            "balances",
+            "balances__checkpoints",
+            "balances__changelog",
+            Strategy::EveryBlock,
+        );
+        let storage = deps.as_mut().storage;
+
+        for i in 0..10 {
+            balances.save(storage, i, &100, 1).unwrap();
+        }
+        // Changed between 5 and 10
+        balances.save(storage, 2, &50, 6).unwrap();
+        balances.save(storage, 3, &150, 7).unwrap();
+        balances.remove(storage, 8, 8).unwrap();
+        // Reverted before 10
+        balances.save(storage, 4, &0, 6).unwrap();
+        balances.save(storage, 4, &100, 7).unwrap();
+        // Changed after 10
+        balances.save(storage, 5, &0, 12).unwrap();
+
+        let query: SnapshotDiffPage<5, u32> = SnapshotDiffPage {
+            from: 5,
+            to: 10,
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_snapshot_diff_pagination(deps.as_ref().storage, &balances)
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec![
+                SnapshotDiff {
+                    key: 2,
+                    old: Some(100),
+                    new: Some(50)
+                },
+                SnapshotDiff {
+                    key: 3,
+                    old: Some(100),
+                    new: Some(150)
+                },
+            ]
+        );
+        assert_eq!(res.next, Some(4));
+        // Unchanged keys 0, 1 and 4 were read as well
+        assert_eq!(res.scanned, 5);
+
+        let query: SnapshotDiffPage<5, u32> = SnapshotDiffPage {
+            from: 5,
+            to: 10,
+            start: res.next,
+            qty: None,
+        };
+        let res = query
+            .into_snapshot_diff_pagination(deps.as_ref().storage, &balances)
+            .unwrap();
+        assert_eq!(
+            res.data,
+            vec![SnapshotDiff {
+                key: 8,
+                old: Some(100),
+                new: None
+            }]
+        );
+        assert_eq!(res.next, Some(9));
+        assert_eq!((res.qty, res.scanned), (1, 5));
+    }
+
+    #[test]
+    fn into_snapshot_overlay_pagination() {
+        let mut deps = mock_dependencies();
+        let power: SnapshotMap<String, u64> = SnapshotMap::new(
+            "power",
+            "power__checkpoints",
+            "power__changelog",
+            Strategy::EveryBlock,
+        );
+        let storage = deps.as_mut().storage;
+
+        power.save(storage, "alice".to_string(), &10, 1).unwrap();
+        power.save(storage, "bob".to_string(), &20, 1).unwrap();
+        power.save(storage, "carol".to_string(), &30, 1).unwrap();
+        // Proposal starts at height 5
+        power.save(storage, "alice".to_string(), &15, 6).unwrap();
+        power.remove(storage, "bob".to_string(), 7).unwrap();
+        power.save(storage, "dave".to_string(), &40, 8).unwrap();
+        // Added and removed after the proposal started
+        power.save(storage, "eve".to_string(), &1, 8).unwrap();
+        power.remove(storage, "eve".to_string(), 9).unwrap();
+
+        let query: SnapshotOverlayPage<10, String> = SnapshotOverlayPage {
+            height: 5,
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_snapshot_overlay_pagination(deps.as_ref().storage, &power)
+            .unwrap();
+        let overlay = |key: &str, snapshot, live| SnapshotOverlay {
+            key: key.to_string(),
+            snapshot,
+            live,
+        };
+        // Changelog order sorts keys by length first, `eve` existed at neither height
+        assert_eq!(
+            res.data,
+            vec![
+                overlay("bob", Some(20), None),
+                overlay("dave", None, Some(40)),
+                overlay("alice", Some(10), Some(15)),
+                overlay("carol", Some(30), Some(30)),
+            ]
+        );
+        assert_eq!(res.scanned, 5);
+        assert_eq!(res.next, Some("carol".to_string()));
+    }
+}
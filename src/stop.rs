@@ -0,0 +1,119 @@
+use crate::{Map, NextPage, Page};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+#[cw_serde]
+pub struct StopNextPage<D, K> {
+    pub page: NextPage<D, K>,
+    /// The predicate fired, the entry after `next` is the one it stopped at
+    pub stopped: bool,
+}
+
+impl<const LIMIT: usize, Key> Page<LIMIT, Key> {
+    /// Same as `into_pagination` but ends the page at the first entry matching `stop`,
+    /// e.g. entries until the first non-expired one. Unlike filtering nothing past that
+    /// entry is scanned, and the entry itself is left out
+    pub fn into_stop_when_pagination<'a, Value, Data, Stop, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        stop: Stop,
+        transform: Function,
+    ) -> StdResult<StopNextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Stop: Fn(&Key::Output, &Value) -> bool,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(self.qty.unwrap_or(LIMIT));
+        let mut data = vec![];
+        let mut end = None;
+        let mut stopped = false;
+
+        for item in range {
+            let (key, value) = item?;
+            if stop(&key, &value) {
+                stopped = true;
+                break;
+            }
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(StopNextPage {
+            page: NextPage {
+                data,
+                next: end,
+                qty: len,
+                scanned: len + usize::from(stopped),
+                errors: vec![],
+            },
+            stopped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, Page};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_stop_when_pagination() {
+        let mut deps = mock_dependencies();
+        // Expiration height keyed by (expiration, id), expired entries come first
+        let expirations: Map<(u64, u32), u32> = Map::new("expirations");
+
+        for id in 0..10 {
+            expirations
+                .save(deps.as_mut().storage, (id as u64 * 10, id), &id)
+                .unwrap();
+        }
+
+        let height = 45;
+        let query: Page<20, (u64, u32)> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_stop_when_pagination(
+                deps.as_ref().storage,
+                &expirations,
+                |(expiration, _), _| *expiration >= height,
+                |_, v| v,
+            )
+            .unwrap();
+        assert!(res.stopped);
+        assert_eq!(res.page.data, vec![0, 1, 2, 3, 4]);
+        assert_eq!(res.page.next, Some((40, 4)));
+
+        let query: Page<3, (u64, u32)> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_stop_when_pagination(
+                deps.as_ref().storage,
+                &expirations,
+                |(expiration, _), _| *expiration >= height,
+                |_, v| v,
+            )
+            .unwrap();
+        assert!(!res.stopped);
+        assert_eq!(res.page.data, vec![0, 1, 2]);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::tuple_cursor::CursorPart;
+use crate::{NextPage, Page, QueryError, TupleCursor};
+use cosmwasm_std::Addr;
+use std::fmt;
+
+/// Key types that round trip through the `Option<String>` cursor of a query message,
+/// single keys render as is and tuples like a [`TupleCursor`]. `Addr` parts are taken
+/// unchecked as they only ever serve as a range bound
+pub trait StringCursor: Sized {
+    fn encode_cursor(&self) -> String;
+    fn decode_cursor(cursor: &str) -> Result<Self, QueryError>;
+}
+
+macro_rules! part_string_cursors {
+    ($($t:ty),*) => {
+        $(
+            impl StringCursor for $t {
+                fn encode_cursor(&self) -> String {
+                    self.encode_part()
+                }
+
+                fn decode_cursor(cursor: &str) -> Result<Self, QueryError> {
+                    Self::decode_part(cursor)
+                }
+            }
+        )*
+    };
+}
+
+part_string_cursors!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, String, Addr);
+
+macro_rules! tuple_string_cursors {
+    ($($t:ident),*) => {
+        impl<$($t: CursorPart),*> StringCursor for ($($t,)*) {
+            fn encode_cursor(&self) -> String {
+                self.to_cursor_string()
+            }
+
+            fn decode_cursor(cursor: &str) -> Result<Self, QueryError> {
+                Self::from_cursor_string(cursor)
+            }
+        }
+    };
+}
+
+tuple_string_cursors!(A, B);
+tuple_string_cursors!(A, B, C);
+tuple_string_cursors!(A, B, C, D);
+
+/// [`StringCursor`] adapter for `TryFrom<String>` and `Display` based conversions,
+/// e.g. `let start: KeyCursor<u64> = msg.start_after.try_into()?`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyCursor<K>(pub K);
+
+impl<K> KeyCursor<K> {
+    pub fn into_inner(self) -> K {
+        self.0
+    }
+}
+
+impl<K: StringCursor> TryFrom<String> for KeyCursor<K> {
+    type Error = QueryError;
+
+    fn try_from(cursor: String) -> Result<Self, Self::Error> {
+        K::decode_cursor(&cursor).map(KeyCursor)
+    }
+}
+
+impl<K: StringCursor> TryFrom<&str> for KeyCursor<K> {
+    type Error = QueryError;
+
+    fn try_from(cursor: &str) -> Result<Self, Self::Error> {
+        K::decode_cursor(cursor).map(KeyCursor)
+    }
+}
+
+impl<K: StringCursor> fmt::Display for KeyCursor<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.encode_cursor())
+    }
+}
+
+impl<const LIMIT: usize> Page<LIMIT, String> {
+    /// Decodes the string cursor of a query message into the map's key type
+    pub fn into_key_page<K: StringCursor>(self) -> Result<Page<LIMIT, K>, QueryError> {
+        Ok(Page {
+            start: self.start.as_deref().map(K::decode_cursor).transpose()?,
+            qty: self.qty,
+        })
+    }
+}
+
+impl<D, K: StringCursor> NextPage<D, K> {
+    /// Encodes the returned cursor back into the string a query message expects
+    pub fn into_string_cursor(self) -> NextPage<D, String> {
+        NextPage {
+            data: self.data,
+            next: self.next.map(|next| next.encode_cursor()),
+            qty: self.qty,
+            scanned: self.scanned,
+            errors: self.errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{KeyCursor, Map, Page, PaginatedQuery, QueryError};
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::Addr;
+
+    #[test]
+    fn string_cursors() {
+        let cursor: KeyCursor<u64> = "42".try_into().unwrap();
+        assert_eq!(cursor.to_string(), "42");
+        let cursor: KeyCursor<u128> = "340282366920938463463374607431768211455"
+            .try_into()
+            .unwrap();
+        assert_eq!(cursor.into_inner(), u128::MAX);
+        let cursor: KeyCursor<Addr> = "osmo1abc".to_string().try_into().unwrap();
+        assert_eq!(cursor.0, Addr::unchecked("osmo1abc"));
+        let cursor: KeyCursor<(Addr, u64)> = "osmo1abc/7".try_into().unwrap();
+        assert_eq!(cursor.0, (Addr::unchecked("osmo1abc"), 7));
+        assert_eq!(cursor.to_string(), "osmo1abc/7");
+
+        assert_eq!(
+            KeyCursor::<u64>::try_from("-1").unwrap_err(),
+            QueryError::CursorDecode {
+                msg: "invalid u64 cursor part -1".to_string()
+            }
+        );
+        assert!(KeyCursor::<(Addr, u64)>::try_from("osmo1abc").is_err());
+
+        let mut deps = mock_dependencies();
+        let orders: Map<(Addr, u64), u8> = Map::new("orders");
+        for i in 0..5 {
+            orders
+                .save(deps.as_mut().storage, (Addr::unchecked("alice"), i), &0)
+                .unwrap();
+        }
+
+        let page: Page<2, String> = Page {
+            start: Some("alice/1".to_string()),
+            qty: None,
+        };
+        let res = page
+            .into_key_page::<(Addr, u64)>()
+            .unwrap()
+            .into_pagination(deps.as_ref().storage, &orders, |(_, id), _| *id)
+            .unwrap()
+            .into_string_cursor();
+        assert_eq!(res.data, vec![2, 3]);
+        assert_eq!(res.next, Some("alice/3".to_string()));
+    }
+}
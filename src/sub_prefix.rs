@@ -0,0 +1,119 @@
+use crate::{Map, NextPage, PaginatedQuery};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultSubPrefixPage<Prefix, Suffix> = SubPrefixPage<50, Prefix, Suffix>;
+
+/// Fixes only the first element of a 3-tuple key and pages over the remaining two, e.g.
+/// every `(B, C)` under `A` for `Map<(A, B, C), V>`. [`crate::PrefixPage`] fixes `(A, B)`
+/// and pages over `C`
+#[cw_serde]
+pub struct SubPrefixPage<const LIMIT: usize, Prefix, Suffix> {
+    pub prefix: Prefix,
+    pub start: Option<Suffix>,
+    pub qty: Option<usize>,
+}
+
+impl<'a, const LIMIT: usize, Key, Prefix, Suffix, Value, Data> PaginatedQuery<'a, Key, Value, Data>
+    for SubPrefixPage<LIMIT, Prefix, Suffix>
+where
+    Key: PrimaryKey<'a, SubPrefix = Prefix, SuperSuffix = Suffix>,
+    Suffix: PrimaryKey<'a> + KeyDeserialize,
+    <Suffix as KeyDeserialize>::Output: 'static,
+    Value: Serialize + DeserializeOwned,
+{
+    type POutput = NextPage<Data, Suffix::Output>;
+    type FuncKey = Suffix::Output;
+
+    fn into_pagination<Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<Self::POutput>
+    where
+        Function: FnOnce(&Self::FuncKey, Value) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let range = map
+            .sub_prefix(self.prefix)
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(limit);
+        let mut data = Vec::with_capacity(limit.min(LIMIT));
+        let mut end = None;
+
+        for item in range {
+            let (key, value) = item?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned: len,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, PaginatedQuery, PrefixPage, SubPrefixPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn three_tuple_keys() {
+        let mut deps = mock_dependencies();
+        let fills: Map<(String, u32, u32), u64> = Map::new("fills");
+
+        for market in ["atom", "osmo"] {
+            for order in 0..3 {
+                for fill in 0..4 {
+                    fills
+                        .save(
+                            deps.as_mut().storage,
+                            (market.to_string(), order, fill),
+                            &(order as u64 * 10 + fill as u64),
+                        )
+                        .unwrap();
+                }
+            }
+        }
+
+        // Prefix on (market, order), the cursor is the fill only
+        let query: PrefixPage<3, (String, u32, u32), (String, u32), u32> = PrefixPage {
+            prefix: ("osmo".to_string(), 1),
+            start: Some(0),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &fills, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![1, 2, 3]);
+        assert_eq!(res.next, Some(3));
+
+        // Prefix on market, the cursor is (order, fill)
+        let query: SubPrefixPage<3, String, (u32, u32)> = SubPrefixPage {
+            prefix: "osmo".to_string(),
+            start: Some((1, 2)),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &fills, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![13, 20, 21]);
+        assert_eq!(res.next, Some((2, 1)));
+    }
+}
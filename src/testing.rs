@@ -0,0 +1,179 @@
+use crate::{Map, NextPage, Page, PaginatedQuery};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+use std::fmt::Debug;
+
+/// Asserts the page size and cursor, and that `qty` matches the returned data
+#[track_caller]
+pub fn assert_page<D, K>(page: &NextPage<D, K>, expected_len: usize, expected_next: Option<K>)
+where
+    K: PartialEq + Debug,
+{
+    assert_eq!(page.data.len(), expected_len, "page length");
+    assert_eq!(page.qty, expected_len, "page qty");
+    assert_eq!(page.next, expected_next, "page cursor");
+}
+
+/// Pages through the whole map `page_size` entries at a time and asserts the result
+/// matches a single direct range, the panicking counterpart of `check_full_scan`
+#[track_caller]
+pub fn assert_full_scan<'a, Key, Value>(
+    storage: &'a dyn Storage,
+    map: &Map<'a, Key, Value>,
+    page_size: usize,
+) where
+    Key: PrimaryKey<'a> + KeyDeserialize<Output = Key> + Clone + PartialEq + Debug + 'static,
+    Value: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let expected: Vec<(Key, Value)> = map
+        .range(storage, None, None, Order::Ascending)
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    let mut paged = vec![];
+    let mut start = None;
+    loop {
+        let page: Page<1, Key> = Page {
+            start,
+            qty: Some(page_size),
+        };
+        let res = page
+            .into_pagination(storage, map, |k, v| (k.clone(), v))
+            .unwrap();
+
+        assert!(res.qty <= page_size, "page exceeds {} entries", page_size);
+        assert_eq!(res.qty, res.data.len(), "page qty");
+        if res.data.is_empty() {
+            assert_eq!(res.next, None, "page cursor");
+            break;
+        }
+
+        assert_eq!(
+            res.next.as_ref(),
+            res.data.last().map(|(k, _)| k),
+            "page cursor"
+        );
+        start = res.next;
+        paged.extend(res.data);
+    }
+
+    assert_eq!(paged, expected, "paged entries");
+}
+
+/// Saves the value returned by `value` under every key, e.g.
+/// `seed_map(storage, &MAP, 0..100, |i| format!("string-{}", i))`
+#[track_caller]
+pub fn seed_map<'a, Key, Value, Keys, Function>(
+    storage: &mut dyn Storage,
+    map: &Map<'a, Key, Value>,
+    keys: Keys,
+    mut value: Function,
+) where
+    Key: PrimaryKey<'a>,
+    Value: Serialize + DeserializeOwned,
+    Keys: IntoIterator<Item = Key>,
+    Function: FnMut(&Key) -> Value,
+{
+    for key in keys {
+        let data = value(&key);
+        map.save(storage, key, &data).unwrap();
+    }
+}
+
+/// [`seed_map`] for tuple keyed maps, saving every suffix under the same `prefix`
+#[track_caller]
+pub fn seed_prefixed_map<'a, Prefix, Suffix, Value, Suffixes, Function>(
+    storage: &mut dyn Storage,
+    map: &Map<'a, (Prefix, Suffix), Value>,
+    prefix: Prefix,
+    suffixes: Suffixes,
+    mut value: Function,
+) where
+    (Prefix, Suffix): PrimaryKey<'a>,
+    Prefix: Clone,
+    Value: Serialize + DeserializeOwned,
+    Suffixes: IntoIterator<Item = Suffix>,
+    Function: FnMut(&Suffix) -> Value,
+{
+    for suffix in suffixes {
+        let data = value(&suffix);
+        map.save(storage, (prefix.clone(), suffix), &data).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        assert_full_scan, assert_page, seed_map, seed_prefixed_map, Map, Page, PaginatedQuery,
+        PrefixPage,
+    };
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn helpers() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, String> = Map::new("test_map");
+
+        for i in 0..25 {
+            test_map
+                .save(deps.as_mut().storage, i * 3, &format!("string-{}", i))
+                .unwrap();
+        }
+
+        let query: Page<10, u32> = Page {
+            start: Some(60),
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+        assert_page(&res, 4, Some(72));
+
+        for page_size in [1, 7, 25, 40] {
+            assert_full_scan(deps.as_ref().storage, &test_map, page_size);
+        }
+    }
+
+    #[test]
+    fn seeding() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u8, u16> = Map::new("test_map");
+        let orders: Map<(&str, u32), u32> = Map::new("orders");
+
+        seed_map(deps.as_mut().storage, &test_map, 0..100, |i| *i as u16 * 2);
+        seed_prefixed_map(deps.as_mut().storage, &orders, "alice", 0..10, |i| i + 1);
+        seed_prefixed_map(deps.as_mut().storage, &orders, "bob", 0..5, |i| i + 100);
+
+        assert_eq!(test_map.load(deps.as_ref().storage, 42).unwrap(), 84);
+        assert_full_scan(deps.as_ref().storage, &test_map, 30);
+
+        let query: PrefixPage<10, (&str, u32), &str, u32> = PrefixPage {
+            prefix: "bob",
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &orders, |_, v| v)
+            .unwrap();
+        assert_eq!(res.data, vec![100, 101, 102, 103, 104]);
+    }
+
+    #[test]
+    #[should_panic(expected = "page cursor")]
+    fn assert_page_cursor() {
+        let mut deps = mock_dependencies();
+        let test_map: Map<u32, u32> = Map::new("test_map");
+        test_map.save(deps.as_mut().storage, 1, &1).unwrap();
+
+        let query: Page<10, u32> = Page {
+            start: None,
+            qty: None,
+        };
+        let res = query
+            .into_pagination(deps.as_ref().storage, &test_map, |_, v| v)
+            .unwrap();
+        assert_page(&res, 1, Some(2));
+    }
+}
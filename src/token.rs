@@ -0,0 +1,182 @@
+use crate::{NextPage, QueryError};
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{from_json, to_json_vec, Binary, Order, StdResult};
+
+/// Encodes an opaque cursor for clients, prefixed with the key format `version`. Bumping
+/// the version when migrating key types makes stale cursors fail instead of being misread
+pub fn encode_cursor<K: Serialize>(version: u8, cursor: &K) -> StdResult<Binary> {
+    let mut token = vec![version];
+    token.extend(to_json_vec(cursor)?);
+    Ok(token.into())
+}
+
+/// Decodes a cursor from [`encode_cursor`], rejecting cursors issued for another version
+pub fn decode_cursor<K: DeserializeOwned>(version: u8, token: &[u8]) -> Result<K, QueryError> {
+    let (found, cursor) = token
+        .split_first()
+        .ok_or_else(|| QueryError::CursorDecode {
+            msg: "empty cursor".to_string(),
+        })?;
+    if *found != version {
+        return Err(QueryError::CursorVersion {
+            expected: version,
+            found: *found,
+        });
+    }
+    from_json(cursor).map_err(|e| QueryError::CursorDecode { msg: e.to_string() })
+}
+
+/// Same as [`encode_cursor`] but also locks the cursor to the iteration `order`, so clients
+/// flipping the direction mid-stream are rejected instead of receiving duplicated or
+/// skipped entries
+pub fn encode_directed_cursor<K: Serialize>(
+    version: u8,
+    order: Order,
+    cursor: &K,
+) -> StdResult<Binary> {
+    let mut token = vec![version, direction_byte(order)];
+    token.extend(to_json_vec(cursor)?);
+    Ok(token.into())
+}
+
+/// Decodes a cursor from [`encode_directed_cursor`], rejecting cursors issued for another
+/// version or iteration order
+pub fn decode_directed_cursor<K: DeserializeOwned>(
+    version: u8,
+    order: Order,
+    token: &[u8],
+) -> Result<K, QueryError> {
+    let (found, token) = token
+        .split_first()
+        .ok_or_else(|| QueryError::CursorDecode {
+            msg: "empty cursor".to_string(),
+        })?;
+    if *found != version {
+        return Err(QueryError::CursorVersion {
+            expected: version,
+            found: *found,
+        });
+    }
+    let (direction, cursor) = token
+        .split_first()
+        .ok_or_else(|| QueryError::CursorDecode {
+            msg: "missing cursor direction".to_string(),
+        })?;
+    let found = match direction {
+        0 => Order::Ascending,
+        1 => Order::Descending,
+        _ => {
+            return Err(QueryError::CursorDecode {
+                msg: format!("unknown cursor direction {}", direction),
+            })
+        }
+    };
+    if found != order {
+        return Err(QueryError::CursorDirection {
+            expected: direction_name(order).to_string(),
+            found: direction_name(found).to_string(),
+        });
+    }
+    from_json(cursor).map_err(|e| QueryError::CursorDecode { msg: e.to_string() })
+}
+
+fn direction_name(order: Order) -> &'static str {
+    match order {
+        Order::Ascending => "ascending",
+        Order::Descending => "descending",
+    }
+}
+
+fn direction_byte(order: Order) -> u8 {
+    match order {
+        Order::Ascending => 0,
+        Order::Descending => 1,
+    }
+}
+
+impl<D, K: Serialize> NextPage<D, K> {
+    /// Opaque cursor for the next page, see [`encode_cursor`]
+    pub fn encode_next(&self, version: u8) -> StdResult<Option<Binary>> {
+        self.next
+            .as_ref()
+            .map(|next| encode_cursor(version, next))
+            .transpose()
+    }
+
+    /// Opaque cursor for the next page locked to `order`, see [`encode_directed_cursor`]
+    pub fn encode_directed_next(&self, version: u8, order: Order) -> StdResult<Option<Binary>> {
+        self.next
+            .as_ref()
+            .map(|next| encode_directed_cursor(version, order, next))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{decode_cursor, decode_directed_cursor, encode_cursor, NextPage, QueryError};
+    use cosmwasm_std::Order;
+
+    #[test]
+    fn versioned_cursor() {
+        let page = NextPage {
+            data: vec![1, 2, 3],
+            next: Some(3u64),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+        let token = page.encode_next(1).unwrap().unwrap();
+        assert_eq!(decode_cursor::<u64>(1, &token).unwrap(), 3);
+
+        // Keys migrated from u64 to String
+        assert_eq!(
+            decode_cursor::<String>(2, &token).unwrap_err(),
+            QueryError::CursorVersion {
+                expected: 2,
+                found: 1
+            }
+        );
+
+        let token = encode_cursor(2, &"addr".to_string()).unwrap();
+        assert_eq!(decode_cursor::<String>(2, &token).unwrap(), "addr");
+        assert!(matches!(
+            decode_cursor::<String>(2, &[]),
+            Err(QueryError::CursorDecode { .. })
+        ));
+    }
+
+    #[test]
+    fn directed_cursor() {
+        let page = NextPage {
+            data: vec![9, 8, 7],
+            next: Some(7u64),
+            qty: 3,
+            scanned: 3,
+            errors: vec![],
+        };
+        let token = page
+            .encode_directed_next(1, Order::Descending)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            decode_directed_cursor::<u64>(1, Order::Descending, &token).unwrap(),
+            7
+        );
+        assert_eq!(
+            decode_directed_cursor::<u64>(1, Order::Ascending, &token).unwrap_err(),
+            QueryError::CursorDirection {
+                expected: "ascending".to_string(),
+                found: "descending".to_string()
+            }
+        );
+        assert_eq!(
+            decode_directed_cursor::<u64>(2, Order::Descending, &token).unwrap_err(),
+            QueryError::CursorVersion {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use crate::QueryError;
+use cosmwasm_std::Addr;
+
+const DELIMITER: char = '/';
+const ESCAPE: char = '\\';
+
+/// Single element of a [`TupleCursor`]
+pub trait CursorPart: Sized {
+    fn encode_part(&self) -> String;
+    fn decode_part(part: &str) -> Result<Self, QueryError>;
+}
+
+macro_rules! int_cursor_parts {
+    ($($t:ty),*) => {
+        $(
+            impl CursorPart for $t {
+                fn encode_part(&self) -> String {
+                    self.to_string()
+                }
+
+                fn decode_part(part: &str) -> Result<Self, QueryError> {
+                    part.parse().map_err(|_| QueryError::CursorDecode {
+                        msg: format!("invalid {} cursor part {}", stringify!($t), part),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+int_cursor_parts!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl CursorPart for String {
+    fn encode_part(&self) -> String {
+        self.clone()
+    }
+
+    fn decode_part(part: &str) -> Result<Self, QueryError> {
+        Ok(part.to_string())
+    }
+}
+
+impl CursorPart for Addr {
+    fn encode_part(&self) -> String {
+        self.to_string()
+    }
+
+    fn decode_part(part: &str) -> Result<Self, QueryError> {
+        Ok(Addr::unchecked(part))
+    }
+}
+
+/// Renders tuple cursors as readable `/` joined strings such as `"osmo1.../42/7"` for
+/// CLI tools and frontends. Delimiters and backslashes inside parts are backslash escaped
+pub trait TupleCursor: Sized {
+    fn to_cursor_string(&self) -> String;
+    fn from_cursor_string(cursor: &str) -> Result<Self, QueryError>;
+}
+
+macro_rules! tuple_cursors {
+    ($len:literal: $($t:ident $i:tt),*) => {
+        impl<$($t: CursorPart),*> TupleCursor for ($($t,)*) {
+            fn to_cursor_string(&self) -> String {
+                [$(escape(&self.$i.encode_part())),*].join("/")
+            }
+
+            fn from_cursor_string(cursor: &str) -> Result<Self, QueryError> {
+                let parts = split(cursor)?;
+                if parts.len() != $len {
+                    return Err(QueryError::CursorDecode {
+                        msg: format!("expected {} cursor parts, found {}", $len, parts.len()),
+                    });
+                }
+                Ok(($($t::decode_part(&parts[$i])?,)*))
+            }
+        }
+    };
+}
+
+tuple_cursors!(2: A 0, B 1);
+tuple_cursors!(3: A 0, B 1, C 2);
+tuple_cursors!(4: A 0, B 1, C 2, D 3);
+
+fn escape(part: &str) -> String {
+    let mut escaped = String::with_capacity(part.len());
+    for c in part.chars() {
+        if c == DELIMITER || c == ESCAPE {
+            escaped.push(ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn split(cursor: &str) -> Result<Vec<String>, QueryError> {
+    let mut parts = vec![];
+    let mut part = String::new();
+    let mut chars = cursor.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE => match chars.next() {
+                Some(escaped) => part.push(escaped),
+                None => {
+                    return Err(QueryError::CursorDecode {
+                        msg: "cursor ends with an escape".to_string(),
+                    })
+                }
+            },
+            DELIMITER => parts.push(std::mem::take(&mut part)),
+            c => part.push(c),
+        }
+    }
+    parts.push(part);
+
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{QueryError, TupleCursor};
+    use cosmwasm_std::Addr;
+
+    #[test]
+    fn tuple_cursor() {
+        let cursor = (Addr::unchecked("osmo1abc"), 42u64, 7u8);
+        let encoded = cursor.to_cursor_string();
+        assert_eq!(encoded, "osmo1abc/42/7");
+        assert_eq!(
+            <(Addr, u64, u8)>::from_cursor_string(&encoded).unwrap(),
+            cursor
+        );
+
+        let cursor = ("a/b\\c".to_string(), -3i32);
+        let encoded = cursor.to_cursor_string();
+        assert_eq!(encoded, "a\\/b\\\\c/-3");
+        assert_eq!(
+            <(String, i32)>::from_cursor_string(&encoded).unwrap(),
+            cursor
+        );
+
+        assert!(matches!(
+            <(String, u64)>::from_cursor_string("osmo/42/7"),
+            Err(QueryError::CursorDecode { .. })
+        ));
+        assert!(matches!(
+            <(String, u64)>::from_cursor_string("osmo/abc"),
+            Err(QueryError::CursorDecode { .. })
+        ));
+        assert!(matches!(
+            <(String, u64)>::from_cursor_string("osmo/42\\"),
+            Err(QueryError::CursorDecode { .. })
+        ));
+    }
+}
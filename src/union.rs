@@ -0,0 +1,153 @@
+use crate::error::deserialize_key;
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+pub type DefaultUnionPage<K> = UnionPage<50, K>;
+
+/// Pages over two maps sharing the same key type as if they were one,
+/// when a key is present in both maps the entry from the first one is used
+#[cw_serde]
+pub struct UnionPage<const LIMIT: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+}
+
+impl<const LIMIT: usize, Key> UnionPage<LIMIT, Key> {
+    pub fn into_union_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        first: &Map<'a, Key, Value>,
+        second: &Map<'a, Key, Value>,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned + 'a,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let mut first_range = first
+            .range_raw(
+                storage,
+                self.start
+                    .clone()
+                    .map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+        let mut second_range = second
+            .range_raw(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .peekable();
+
+        let limit = self.qty.unwrap_or(LIMIT);
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        while data.len() < limit {
+            let ordering = match (first_range.peek(), second_range.peek()) {
+                (None, None) => break,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(Err(_)), _) => Ordering::Less,
+                (_, Some(Err(_))) => Ordering::Greater,
+                (Some(Ok((a, _))), Some(Ok((b, _)))) => a.cmp(b),
+            };
+
+            let (raw_key, value) = match ordering {
+                Ordering::Less => {
+                    scanned += 1;
+                    first_range.next()
+                }
+                Ordering::Greater => {
+                    scanned += 1;
+                    second_range.next()
+                }
+                Ordering::Equal => {
+                    scanned += 2;
+                    second_range.next();
+                    first_range.next()
+                }
+            }
+            .unwrap()?;
+
+            let key = deserialize_key::<Key>(raw_key)?;
+            data.push(transform(&key, value));
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Map, UnionPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_union_pagination() {
+        let mut deps = mock_dependencies();
+        let active: Map<u32, String> = Map::new("active");
+        let archived: Map<u32, String> = Map::new("archived");
+
+        for i in 0..100 {
+            if i % 3 == 0 {
+                archived
+                    .save(deps.as_mut().storage, i, &format!("archived-{}", i))
+                    .unwrap();
+            }
+            if i % 3 != 0 || i % 2 == 0 {
+                active
+                    .save(deps.as_mut().storage, i, &format!("active-{}", i))
+                    .unwrap();
+            }
+        }
+
+        let query: UnionPage<20, _> = UnionPage {
+            start: None,
+            qty: None,
+        };
+
+        let res = query
+            .into_union_pagination(deps.as_ref().storage, &active, &archived, |_, v| v)
+            .unwrap();
+
+        assert_eq!(res.qty, 20);
+        assert_eq!(res.next, Some(19));
+        assert_eq!(res.data.first().unwrap(), "active-0");
+        assert_eq!(res.data.get(3).unwrap(), "archived-3");
+        assert_eq!(res.data.get(6).unwrap(), "active-6");
+
+        let query: UnionPage<20, _> = UnionPage {
+            start: Some(95),
+            qty: None,
+        };
+
+        let res = query
+            .into_union_pagination(deps.as_ref().storage, &active, &archived, |k, _| *k)
+            .unwrap();
+
+        assert_eq!(res.data, vec![96, 97, 98, 99]);
+        assert_eq!(res.next, Some(99));
+    }
+}
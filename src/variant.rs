@@ -0,0 +1,170 @@
+use crate::{Map, NextPage};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{to_json_vec, Order, StdError, StdResult, Storage};
+use cw_storage_plus::{Bound, KeyDeserialize, PrimaryKey};
+use std::marker::PhantomData;
+
+pub type DefaultVariantPage<K> = VariantPage<50, 500, K>;
+
+/// Page over enum values that only returns the listed `variants`, e.g. active proposals,
+/// scanning at most `MAX_SCAN` entries. No variants returns every entry
+#[cw_serde]
+pub struct VariantPage<const LIMIT: usize, const MAX_SCAN: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    #[serde(default)]
+    pub variants: Vec<String>,
+    pub max_scan: Option<usize>,
+}
+
+impl<const LIMIT: usize, const MAX_SCAN: usize, Key> VariantPage<LIMIT, MAX_SCAN, Key> {
+    /// `variant` names the variant of a value, usually a `match` over the enum or
+    /// [`serde_variant`]
+    pub fn into_variant_pagination<'a, Value, Data, Name, Variant, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &Map<'a, Key, Value>,
+        variant: Variant,
+        transform: Function,
+    ) -> StdResult<NextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Name: AsRef<str>,
+        Variant: Fn(&Value) -> StdResult<Name>,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let limit = self.qty.unwrap_or(LIMIT);
+        let max_scan = self.max_scan.unwrap_or(MAX_SCAN).min(MAX_SCAN);
+        let range = map
+            .range(
+                storage,
+                self.start.map(|s| Bound::Exclusive((s, PhantomData))),
+                None,
+                Order::Ascending,
+            )
+            .take(max_scan);
+        let mut data = vec![];
+        let mut end = None;
+        let mut scanned = 0;
+
+        for item in range {
+            if data.len() >= limit {
+                break;
+            }
+
+            let (key, value) = item?;
+            scanned += 1;
+            let matches = self.variants.is_empty() || {
+                let name = variant(&value)?;
+                self.variants.iter().any(|v| v == name.as_ref())
+            };
+            if matches {
+                data.push(transform(&key, value));
+            }
+
+            end = Some(key);
+        }
+
+        let len = data.len();
+        Ok(NextPage {
+            data,
+            next: end,
+            qty: len,
+            scanned,
+            errors: vec![],
+        })
+    }
+}
+
+/// Variant name of an externally tagged enum as serialized, e.g. `"active"` for a
+/// `#[cw_serde]` enum. Serializes the whole value, a `match` is cheaper on large values
+pub fn serde_variant<Value: Serialize>(value: &Value) -> StdResult<String> {
+    let json = to_json_vec(value)?;
+    let tag = match json.first() {
+        Some(b'"') => &json[1..],
+        Some(b'{') if json.get(1) == Some(&b'"') => &json[2..],
+        _ => return Err(StdError::generic_err("value is not an enum variant")),
+    };
+    let end = tag
+        .iter()
+        .position(|b| *b == b'"')
+        .ok_or_else(|| StdError::generic_err("value is not an enum variant"))?;
+
+    String::from_utf8(tag[..end].to_vec()).map_err(|e| StdError::generic_err(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{serde_variant, Map, VariantPage};
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::testing::mock_dependencies;
+    use cosmwasm_std::StdResult;
+
+    #[cw_serde]
+    enum Proposal {
+        Active { votes: u32 },
+        Passed { votes: u32 },
+        Rejected,
+    }
+
+    #[test]
+    fn into_variant_pagination() {
+        let mut deps = mock_dependencies();
+        let proposals: Map<u32, Proposal> = Map::new("proposals");
+
+        for id in 0..30 {
+            let proposal = match id % 3 {
+                0 => Proposal::Active { votes: id },
+                1 => Proposal::Passed { votes: id },
+                _ => Proposal::Rejected,
+            };
+            proposals
+                .save(deps.as_mut().storage, id, &proposal)
+                .unwrap();
+        }
+
+        let name = |proposal: &Proposal| -> StdResult<&'static str> {
+            Ok(match proposal {
+                Proposal::Active { .. } => "active",
+                Proposal::Passed { .. } => "passed",
+                Proposal::Rejected => "rejected",
+            })
+        };
+        let query: VariantPage<3, 100, u32> = VariantPage {
+            start: None,
+            qty: None,
+            variants: vec!["active".to_string()],
+            max_scan: None,
+        };
+        let res = query
+            .into_variant_pagination(deps.as_ref().storage, &proposals, name, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![0, 3, 6]);
+        assert_eq!(res.next, Some(6));
+        assert_eq!(res.scanned, 7);
+
+        let query: VariantPage<3, 100, u32> = VariantPage {
+            start: Some(20),
+            qty: None,
+            variants: vec!["passed".to_string(), "rejected".to_string()],
+            max_scan: Some(4),
+        };
+        let res = query
+            .into_variant_pagination(deps.as_ref().storage, &proposals, serde_variant, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data, vec![22, 23]);
+        assert_eq!(res.next, Some(24));
+        assert_eq!(res.scanned, 4);
+
+        assert_eq!(serde_variant(&Proposal::Rejected).unwrap(), "rejected");
+        assert_eq!(
+            serde_variant(&Proposal::Active { votes: 1 }).unwrap(),
+            "active"
+        );
+        assert!(serde_variant(&1u8).is_err());
+    }
+}
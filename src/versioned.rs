@@ -0,0 +1,178 @@
+use crate::{Item, Map, Page, PaginatedQuery};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{StdResult, Storage};
+use cw_storage_plus::{KeyDeserialize, PrimaryKey};
+
+/// `Map` wrapper that bumps a version counter on every write, letting multi page
+/// exports detect that the map changed between two queries
+pub struct VersionedMap<K, V> {
+    map: Map<'static, K, V>,
+    version: Item<'static, u64>,
+}
+
+impl<K, V> VersionedMap<K, V> {
+    pub const fn new(namespace: &'static str, version_namespace: &'static str) -> Self {
+        VersionedMap {
+            map: Map::new(namespace),
+            version: Item::new(version_namespace),
+        }
+    }
+
+    /// Underlying map for reads, writing through it skips the version bump
+    pub fn map(&self) -> &Map<'static, K, V> {
+        &self.map
+    }
+
+    pub fn version(&self, storage: &dyn Storage) -> StdResult<u64> {
+        Ok(self.version.may_load(storage)?.unwrap_or_default())
+    }
+
+    fn bump(&self, storage: &mut dyn Storage) -> StdResult<u64> {
+        let version = self.version(storage)?.wrapping_add(1);
+        self.version.save(storage, &version)?;
+        Ok(version)
+    }
+}
+
+impl<'a, K, V> VersionedMap<K, V>
+where
+    K: PrimaryKey<'a>,
+    V: Serialize + DeserializeOwned,
+{
+    pub fn load(&self, storage: &dyn Storage, key: K) -> StdResult<V> {
+        self.map.load(storage, key)
+    }
+
+    pub fn may_load(&self, storage: &dyn Storage, key: K) -> StdResult<Option<V>> {
+        self.map.may_load(storage, key)
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage, key: K, value: &V) -> StdResult<()> {
+        self.map.save(storage, key, value)?;
+        self.bump(storage)?;
+        Ok(())
+    }
+
+    pub fn remove(&self, storage: &mut dyn Storage, key: K) -> StdResult<()> {
+        self.map.remove(storage, key);
+        self.bump(storage)?;
+        Ok(())
+    }
+
+    pub fn update<A>(&self, storage: &mut dyn Storage, key: K, action: A) -> StdResult<V>
+    where
+        A: FnOnce(Option<V>) -> StdResult<V>,
+    {
+        let value = self.map.update(storage, key, action)?;
+        self.bump(storage)?;
+        Ok(value)
+    }
+}
+
+pub type DefaultVersionedPage<K> = VersionedPage<50, K>;
+
+#[cw_serde]
+pub struct VersionedPage<const LIMIT: usize, K> {
+    pub start: Option<K>,
+    pub qty: Option<usize>,
+    /// Version returned by the first page of the export
+    pub version: Option<u64>,
+}
+
+#[cw_serde]
+pub struct VersionedNextPage<D, K> {
+    pub data: Vec<D>,
+    pub next: Option<K>,
+    pub qty: usize,
+    /// Version observed at the first page, carried along with the cursor
+    pub version: u64,
+    /// The map was written to since `version`, the pages no longer form a consistent snapshot
+    pub changed: bool,
+}
+
+impl<const LIMIT: usize, Key> VersionedPage<LIMIT, Key> {
+    pub fn into_versioned_pagination<'a, Value, Data, Function>(
+        self,
+        storage: &'a dyn Storage,
+        map: &VersionedMap<Key, Value>,
+        transform: Function,
+    ) -> StdResult<VersionedNextPage<Data, Key::Output>>
+    where
+        Key: PrimaryKey<'a> + KeyDeserialize + Clone,
+        <Key as KeyDeserialize>::Output: 'static,
+        Value: Serialize + DeserializeOwned,
+        Function: FnOnce(&Key::Output, Value) -> Data + Copy,
+    {
+        let current = map.version(storage)?;
+        let version = self.version.unwrap_or(current);
+
+        let page: Page<LIMIT, Key> = Page {
+            start: self.start,
+            qty: self.qty,
+        };
+        let res = page.into_pagination(storage, map.map(), transform)?;
+
+        Ok(VersionedNextPage {
+            data: res.data,
+            next: res.next,
+            qty: res.qty,
+            version,
+            changed: version != current,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{VersionedMap, VersionedPage};
+    use cosmwasm_std::testing::mock_dependencies;
+
+    #[test]
+    fn into_versioned_pagination() {
+        let mut deps = mock_dependencies();
+        let test_map: VersionedMap<u8, u8> = VersionedMap::new("test_map", "test_map__version");
+
+        for i in 0..50 {
+            test_map.save(deps.as_mut().storage, i, &i).unwrap();
+        }
+        assert_eq!(test_map.version(deps.as_ref().storage).unwrap(), 50);
+
+        let query: VersionedPage<20, _> = VersionedPage {
+            start: None,
+            qty: None,
+            version: None,
+        };
+        let res = query
+            .into_versioned_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.version, 50);
+        assert!(!res.changed);
+
+        let query: VersionedPage<20, _> = VersionedPage {
+            start: res.next,
+            qty: None,
+            version: Some(res.version),
+        };
+        let res = query
+            .into_versioned_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.data.first(), Some(&20));
+        assert!(!res.changed);
+
+        test_map.remove(deps.as_mut().storage, 45).unwrap();
+
+        let query: VersionedPage<20, _> = VersionedPage {
+            start: res.next,
+            qty: None,
+            version: Some(res.version),
+        };
+        let res = query
+            .into_versioned_pagination(deps.as_ref().storage, &test_map, |k, _| *k)
+            .unwrap();
+        assert_eq!(res.qty, 9);
+        assert_eq!(res.version, 50);
+        assert!(res.changed);
+    }
+}